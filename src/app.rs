@@ -1,14 +1,21 @@
 use crate::{
+    backup::SaveBackup,
+    components::SaveButton,
     emu::*,
     icon,
     pages::{InitPage, SavePage, SettingsPage},
-    smmdb::{Course2Response, Difficulty, QueryParams, SmmdbUser, SortOptions},
+    save_diff::DiffFormat,
+    save_filter::{CourseTheme, GameStyle, SlotFilter, UploadedFilter},
+    save_watcher::{load_save, SaveFingerprint},
+    settings::RegisteredSave,
+    smmdb::{
+        Course2Response, CourseCount, Difficulty, QueryParams, SmmdbUser, SortDirection, SortValue,
+    },
     styles::*,
-    widgets::SmmdbTab,
-    EmuSave, Page, Progress, Settings, Smmdb,
+    widgets::{SlotOption, SmmdbTab, TransferDirection},
+    EmuSave, EmuType, Page, Progress, Settings, Smmdb,
 };
 
-use futures::future;
 use iced::{
     button, container, executor, Application, Background, Button, Clipboard, Column, Command,
     Container, Element, Length, Row, Space, Subscription,
@@ -16,7 +23,10 @@ use iced::{
 use iced_native::{keyboard, subscription, Event};
 use nfd::Response;
 use smmdb_lib::{CourseEntry, SavedCourse};
-use std::convert::TryInto;
+use std::{convert::TryInto, path::PathBuf, time::Duration};
+
+/// Interval in which followed uploaders are checked for new courses.
+const FOLLOW_CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
 
 pub struct App {
     state: AppState,
@@ -35,6 +45,12 @@ pub enum AppState {
     UploadSelect(SavedCourse),
     SwapSelect(usize),
     DownloadSelect(usize),
+    /// The selected SMMDB course is already installed in another slot and needs confirmation.
+    DownloadDuplicateSelect {
+        save_index: usize,
+        smmdb_id: String,
+    },
+    EditSelect(usize),
     DeleteSelect(usize),
     DeleteSmmdbSelect(String),
     Downloading {
@@ -56,25 +72,99 @@ pub enum Message {
     SetWindowSize(WindowSize),
     OpenSave(EmuSave),
     OpenCustomSave,
-    LoadSave(Box<smmdb_lib::Save>, String),
+    SetDiscoveredSaves(PathBuf, Vec<EmuSave>, bool),
+    SetReadOnly(bool),
+    AddSearchRoot,
+    RemoveSearchRoot(PathBuf),
+    RegisterSaveNameChanged(String),
+    RegisterSaveEmuTypeChanged(EmuType),
+    RegisterSave,
+    RemoveRegisteredSave(PathBuf),
+    EmuScanned(usize, ScanStep, EmuScan),
+    ToggleScanDiagnostics,
+    OpenBackup(bool),
+    OpenSecondSave(bool),
+    LoadSecondSave(
+        Box<smmdb_lib::Save>,
+        String,
+        PathBuf,
+        Option<SaveBackup>,
+        Option<SaveFingerprint>,
+    ),
+    CloseSecondSave,
+    TransferDirectionChanged(TransferDirection),
+    TransferSourceChanged(SlotOption),
+    TransferTargetChanged(SlotOption),
+    TransferCourse(bool),
+    CompareSaves,
+    ExportSaveDiff(DiffFormat),
+    CloseSaveDiff,
+    ExportBackup,
+    LoadSave(
+        Box<smmdb_lib::Save>,
+        String,
+        PathBuf,
+        bool,
+        Option<SaveBackup>,
+        Option<SaveFingerprint>,
+    ),
     LoadSaveError(String),
+    CheckSaveModification,
+    SetRunningEmulators(PathBuf, Vec<RunningEmulator>),
+    ReloadSave,
+    MergeSaveModification,
+    IgnoreSaveModification,
+    SaveError(String),
     FetchSaveCourses(Vec<String>),
     FetchCourses,
     FetchSelfCourses,
     FetchError(String),
     SetSaveCourseResponse(Vec<Course2Response>),
     SetSmmdbCourses(Vec<Course2Response>),
+    SetSmmdbCourseCount(u32, CourseCount),
+    InfiniteScrollChanged(bool),
+    PrefetchCourses,
+    AppendSmmdbCourses(u32, u32, Vec<Course2Response>),
     SetSelfSmmdbCourses(Vec<Course2Response>),
     SetSmmdbCourseThumbnail(Vec<u8>, String),
     SetSmmdbTab(SmmdbTab),
+    OpenUploaderProfile(String, String),
+    SetUploaderProfileCourses(String, Vec<Course2Response>),
+    CloseUploaderProfile,
+    FollowUploader(String, String),
+    UnfollowUploader(String),
+    CheckFollowedUploaders,
+    SetFollowedUploaderCourses(String, Vec<Course2Response>),
+    MarkInboxRead,
+    ToggleFavorite(String),
+    FetchFavorites,
+    SetFavoriteCourses(Vec<Course2Response>),
+    SaveFilterTextChanged(String),
+    SaveFilterGameStyleChanged(GameStyle),
+    SaveFilterThemeChanged(CourseTheme),
+    SaveFilterUploadedChanged(UploadedFilter),
+    SaveFilterDifficultyChanged(Difficulty),
+    SaveFilterSlotChanged(SlotFilter),
+    ResetSaveFilter,
     InitUploadCourse(SavedCourse),
     UploadCourse(SavedCourse),
     UploadSucceeded(SavedCourse, String),
     InitSwapCourse(usize),
     SwapCourse(usize, usize),
+    ExportCourse(usize),
     InitDownloadCourse(usize),
+    InitDownloadDuplicate(usize, String),
+    JumpToSlot(usize),
     DownloadCourse(usize, String),
     DownloadProgressed(Progress),
+    CheckCourseUpdates,
+    SetCourseUpdateResponse(Vec<Course2Response>),
+    UpdateAllCourses,
+    CloseCourseUpdates,
+    InitEditCourse(usize),
+    EditCourseTitleChanged(usize, String),
+    EditCourseDescriptionChanged(usize, String),
+    EditCourse(usize),
     InitDeleteCourse(usize),
     DeleteCourse(usize),
     InitDeleteSmmdbCourse(String),
@@ -83,10 +173,29 @@ pub enum Message {
     TitleChanged(String),
     UploaderChanged(String),
     DifficultyChanged(Difficulty),
-    SortChanged(SortOptions),
+    ToggleAdvancedSearch,
+    TitleExactChanged(bool),
+    TitleCaseSensitiveChanged(bool),
+    TitleTrimmedChanged(bool),
+    OwnerChanged(String),
+    CourseIdChanged(String),
+    CourseIdsChanged(String),
+    CourseLookupChanged(String),
+    LookupCourse,
+    SavedSearchSelected(String),
+    SavedSearchNameChanged(String),
+    SaveSearch,
+    DeleteSavedSearch,
+    SortValueChanged(usize, SortValue),
+    SortDirectionChanged(usize, SortDirection),
+    AddSortKey,
+    RemoveSortKey(usize),
     ApplyFilters,
     PaginateForward,
     PaginateBackward,
+    PageSizeChanged(u32),
+    PageInputChanged(String),
+    JumpToPage,
     PaginateSelfForward,
     PaginateSelfBackward,
     UpvoteCourse(String),
@@ -94,8 +203,8 @@ pub enum Message {
     ResetCourseVote(String),
     SetVoteCourse(String, i32),
     OpenSettings,
-    TrySaveSettings(Settings),
-    SaveSettings((Settings, Option<SmmdbUser>)),
+    TrySaveSettings(Option<String>),
+    SaveSettings((Option<String>, Option<SmmdbUser>)),
     RejectSettings(String),
     CloseSettings,
     ChangeApiKey(String),
@@ -115,16 +224,31 @@ impl Application for App {
     type Flags = ();
 
     fn new(_flags: ()) -> (App, Command<Self::Message>) {
-        let components = guess_emu_dir().unwrap();
         let settings = Settings::load().unwrap();
-        let smmdb = Smmdb::new(settings.apikey.clone());
-        let mut commands = vec![async move { Message::FetchCourses }.into()];
-        if let Some(apikey) = &settings.apikey {
-            let settings = settings.clone();
+        let mut smmdb = Smmdb::new(settings.apikey.clone());
+        if let Some(query_params) = settings.query_params.clone() {
+            smmdb.set_query_params(query_params);
+        }
+        smmdb.set_saved_searches(settings.saved_searches.clone());
+        smmdb.set_followed_uploaders(settings.followed_uploaders.clone());
+        smmdb.set_favorites(settings.favorites.clone());
+        smmdb.set_installed_versions(settings.installed_course_versions.clone());
+        let mut init_page = InitPage::new(vec![], &settings);
+        let scan_generation = init_page.start_scan();
+        let mut commands = vec![
+            async move { Message::FetchCourses }.into(),
+            async move { Message::CheckFollowedUploaders }.into(),
+            scan_emu_step(
+                scan_generation,
+                ScanStep::ALL[0],
+                settings.search_roots.clone(),
+            ),
+        ];
+        if let Some(apikey) = settings.apikey.clone() {
             commands.push(Command::perform(
                 Smmdb::try_sign_in(apikey.clone()),
                 move |res| match res {
-                    Ok(user) => Message::SaveSettings((settings.clone(), Some(user))),
+                    Ok(user) => Message::SaveSettings((Some(apikey.clone()), Some(user))),
                     Err(err) => Message::FetchError(err),
                 },
             ))
@@ -134,7 +258,7 @@ impl Application for App {
                 state: AppState::Default,
                 error_state: AppErrorState::None,
                 settings,
-                current_page: Page::Init(InitPage::new(components)),
+                current_page: Page::Init(init_page),
                 smmdb,
                 _window_size: WindowSize::M,
                 settings_button: button::State::new(),
@@ -155,38 +279,111 @@ impl Application for App {
                 self._window_size = window_size;
                 Command::none()
             }
-            Message::OpenSave(save) => {
-                self.state = AppState::Loading;
-                let display_name = save.get_display_name().clone();
-                Command::perform(
-                    async move {
-                        futures::join!(
-                            smmdb_lib::Save::new(save.get_location().clone()),
-                            future::ok::<String, String>(display_name)
-                        )
-                    },
-                    move |res| match res {
-                        (Ok(smmdb_save), Ok(display_name)) => {
-                            Message::LoadSave(Box::new(smmdb_save), display_name)
+            Message::SetReadOnly(read_only) => {
+                if let Page::Init(ref mut init_page) = self.current_page {
+                    init_page.set_read_only(read_only);
+                }
+                Command::none()
+            }
+            Message::AddSearchRoot => match nfd::open_pick_folder(None) {
+                Ok(Response::Okay(search_root)) => {
+                    let search_root = PathBuf::from(search_root);
+                    if !self.settings.search_roots.contains(&search_root) {
+                        self.settings.search_roots.push(search_root);
+                        return self.save_init_settings(true);
+                    }
+                    Command::none()
+                }
+                Ok(_) => Command::none(),
+                Err(err) => async move { Message::LoadSaveError(format!("{:?}", err)) }.into(),
+            },
+            Message::RemoveSearchRoot(search_root) => {
+                self.settings
+                    .search_roots
+                    .retain(|path| path != &search_root);
+                self.save_init_settings(true)
+            }
+            Message::RegisterSaveNameChanged(name) => {
+                if let Page::Init(ref mut init_page) = self.current_page {
+                    init_page.set_register_name(name);
+                }
+                Command::none()
+            }
+            Message::RegisterSaveEmuTypeChanged(emu_type) => {
+                if let Page::Init(ref mut init_page) = self.current_page {
+                    init_page.set_register_emu_type(emu_type);
+                }
+                Command::none()
+            }
+            Message::RegisterSave => {
+                let (display_name, emu_type) =
+                    if let Page::Init(ref mut init_page) = self.current_page {
+                        init_page.take_register_values()
+                    } else {
+                        return Command::none();
+                    };
+                match nfd::open_pick_folder(None) {
+                    Ok(Response::Okay(location)) => {
+                        let location = PathBuf::from(location);
+                        let display_name = if display_name.trim().is_empty() {
+                            location.to_string_lossy().into()
+                        } else {
+                            display_name
+                        };
+                        self.settings
+                            .registered_saves
+                            .retain(|save| save.location != location);
+                        self.settings.registered_saves.push(RegisteredSave {
+                            display_name,
+                            location,
+                            emu_type,
+                        });
+                        self.save_init_settings(false)
+                    }
+                    Ok(_) => Command::none(),
+                    Err(err) => async move { Message::LoadSaveError(format!("{:?}", err)) }.into(),
+                }
+            }
+            Message::RemoveRegisteredSave(location) => {
+                self.settings
+                    .registered_saves
+                    .retain(|save| save.location != location);
+                self.save_init_settings(false)
+            }
+            Message::EmuScanned(generation, step, scan) => {
+                if let Page::Init(ref mut init_page) = self.current_page {
+                    if init_page.add_scan(generation, scan) {
+                        if let Some(next_step) = step.next() {
+                            return scan_emu_step(
+                                generation,
+                                next_step,
+                                self.settings.search_roots.clone(),
+                            );
                         }
-                        (Err(err), _) => Message::LoadSaveError(err.into()),
-                        _ => todo!(),
-                    },
-                )
+                        init_page.finish_scan();
+                    }
+                }
+                Command::none()
+            }
+            Message::ToggleScanDiagnostics => {
+                if let Page::Init(ref mut init_page) = self.current_page {
+                    init_page.toggle_diagnostics();
+                }
+                Command::none()
+            }
+            Message::OpenSave(save) => {
+                let read_only = self.is_read_only_requested();
+                self.open_save(save, read_only)
             }
             Message::OpenCustomSave => {
                 self.state = AppState::Loading;
+                let read_only = self.is_read_only_requested();
                 match nfd::open_pick_folder(None) {
                     Ok(result) => match result {
                         Response::Okay(file_path) => {
-                            Command::perform(smmdb_lib::Save::new(file_path.clone()), move |res| {
-                                match res {
-                                    Ok(smmdb_save) => Message::LoadSave(
-                                        Box::new(smmdb_save),
-                                        file_path.clone().to_string_lossy().into(),
-                                    ),
-                                    Err(err) => Message::LoadSaveError(err.into()),
-                                }
+                            let root = file_path.clone();
+                            Command::perform(async move { discover_saves(&root) }, move |saves| {
+                                Message::SetDiscoveredSaves(file_path.clone(), saves, read_only)
                             })
                         }
                         Response::OkayMultiple(_files) => {
@@ -201,27 +398,250 @@ impl Application for App {
                     Err(err) => async move { Message::LoadSaveError(format!("{:?}", err)) }.into(),
                 }
             }
-            Message::LoadSave(smmdb_save, display_name) => {
+            Message::SetDiscoveredSaves(root, mut saves, read_only) => {
+                if let Page::Init(ref mut init_page) = self.current_page {
+                    init_page.set_discovered_saves(vec![]);
+                }
+                match saves.len() {
+                    0 => {
+                        self.state = AppState::Default;
+                        self.error_state = AppErrorState::Some(format!(
+                            "No Super Mario Maker 2 save found in {}",
+                            root.to_string_lossy()
+                        ));
+                        Command::none()
+                    }
+                    1 => {
+                        let save = saves.remove(0);
+                        self.open_save(save, read_only)
+                    }
+                    _ => {
+                        self.state = AppState::Default;
+                        self.error_state = AppErrorState::None;
+                        if let Page::Init(ref mut init_page) = self.current_page {
+                            init_page.set_discovered_saves(
+                                saves.into_iter().map(SaveButton::from_save).collect(),
+                            );
+                        }
+                        Command::none()
+                    }
+                }
+            }
+            Message::OpenBackup(is_zip) => {
+                let response = if is_zip {
+                    nfd::open_file_dialog(Some("zip"), None)
+                } else {
+                    nfd::open_pick_folder(None)
+                };
+                match response {
+                    Ok(Response::Okay(source)) => {
+                        self.state = AppState::Loading;
+                        let read_only = self.is_read_only_requested();
+                        Command::perform(
+                            async move {
+                                let backup = SaveBackup::open(PathBuf::from(source))?;
+                                let (smmdb_save, fingerprint) =
+                                    load_save(backup.get_working_dir().clone()).await?;
+                                Ok::<_, anyhow::Error>((smmdb_save, fingerprint, backup))
+                            },
+                            move |res| match res {
+                                Ok((smmdb_save, fingerprint, backup)) => Message::LoadSave(
+                                    Box::new(smmdb_save),
+                                    backup.get_display_name(),
+                                    backup.get_working_dir().clone(),
+                                    read_only,
+                                    Some(backup),
+                                    fingerprint,
+                                ),
+                                Err(err) => Message::LoadSaveError(err.to_string()),
+                            },
+                        )
+                    }
+                    Ok(_) => Command::none(),
+                    Err(err) => async move { Message::LoadSaveError(format!("{:?}", err)) }.into(),
+                }
+            }
+            Message::OpenSecondSave(false) => match nfd::open_pick_folder(None) {
+                Ok(Response::Okay(location)) => {
+                    self.state = AppState::Loading;
+                    let location = PathBuf::from(location);
+                    Command::perform(load_save(location.clone()), move |res| match res {
+                        Ok((smmdb_save, fingerprint)) => Message::LoadSecondSave(
+                            Box::new(smmdb_save),
+                            location.to_string_lossy().into(),
+                            location.clone(),
+                            None,
+                            fingerprint,
+                        ),
+                        Err(err) => Message::SaveError(err.to_string()),
+                    })
+                }
+                Ok(_) => Command::none(),
+                Err(err) => async move { Message::SaveError(format!("{:?}", err)) }.into(),
+            },
+            Message::OpenSecondSave(true) => match nfd::open_file_dialog(Some("zip"), None) {
+                Ok(Response::Okay(source)) => {
+                    self.state = AppState::Loading;
+                    Command::perform(
+                        async move {
+                            let backup = SaveBackup::open(PathBuf::from(source))?;
+                            let (smmdb_save, fingerprint) =
+                                load_save(backup.get_working_dir().clone()).await?;
+                            Ok::<_, anyhow::Error>((smmdb_save, fingerprint, backup))
+                        },
+                        move |res| match res {
+                            Ok((smmdb_save, fingerprint, backup)) => Message::LoadSecondSave(
+                                Box::new(smmdb_save),
+                                backup.get_display_name(),
+                                backup.get_working_dir().clone(),
+                                Some(backup),
+                                fingerprint,
+                            ),
+                            Err(err) => Message::SaveError(err.to_string()),
+                        },
+                    )
+                }
+                Ok(_) => Command::none(),
+                Err(err) => async move { Message::SaveError(format!("{:?}", err)) }.into(),
+            },
+            Message::LoadSecondSave(smmdb_save, display_name, location, backup, fingerprint) => {
+                self.state = AppState::Default;
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    if save_page.get_location() == &location {
+                        self.error_state = AppErrorState::Some(
+                            "This save is already opened. Please select another save.".to_string(),
+                        );
+                        return Command::none();
+                    }
+                    let course_ids = get_smmdb_ids(&smmdb_save);
+                    // A backup opened as second save can not be exported, so changes to its
+                    // working copy would be lost.
+                    let read_only = save_page.is_read_only() || backup.is_some();
+                    save_page.set_other(SavePage::new(
+                        *smmdb_save,
+                        display_name,
+                        location,
+                        read_only,
+                        backup,
+                        fingerprint,
+                        self.smmdb.get_course_responses(),
+                    ));
+                    if !course_ids.is_empty() {
+                        return async move { Message::FetchSaveCourses(course_ids) }.into();
+                    }
+                }
+                Command::none()
+            }
+            Message::CloseSecondSave => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.close_other();
+                }
+                Command::none()
+            }
+            Message::TransferDirectionChanged(direction) => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.set_transfer_direction(direction);
+                }
+                Command::none()
+            }
+            Message::TransferSourceChanged(slot) => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.set_transfer_source(slot);
+                }
+                Command::none()
+            }
+            Message::TransferTargetChanged(slot) => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.set_transfer_target(slot);
+                }
+                Command::none()
+            }
+            Message::TransferCourse(is_move) => match self.current_page {
+                Page::Save(ref mut save_page) => {
+                    self.state = AppState::Loading;
+                    let fut = save_page.transfer_course(is_move, self.smmdb.get_course_responses());
+                    // TODO find better way than block_on
+                    match futures::executor::block_on(fut) {
+                        Ok(()) => async { Message::ResetState }.into(),
+                        Err(err) => async move { Message::SaveError(err.to_string()) }.into(),
+                    }
+                }
+                _ => Command::none(),
+            },
+            Message::CompareSaves => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    if let Err(err) = save_page.compare_with_other() {
+                        return async move { Message::SaveError(err.to_string()) }.into();
+                    }
+                }
+                Command::none()
+            }
+            Message::ExportSaveDiff(format) => {
+                if let Page::Save(ref save_page) = self.current_page {
+                    let res = match nfd::open_save_dialog(Some(format.get_extension()), None) {
+                        Ok(Response::Okay(path)) => save_page
+                            .export_diff(format)
+                            .and_then(|diff| Ok(std::fs::write(path, diff)?)),
+                        Ok(_) => Ok(()),
+                        Err(err) => Err(anyhow::anyhow!("{:?}", err)),
+                    };
+                    if let Err(err) = res {
+                        return async move { Message::SaveError(err.to_string()) }.into();
+                    }
+                }
+                Command::none()
+            }
+            Message::CloseSaveDiff => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.close_diff();
+                }
+                Command::none()
+            }
+            Message::ExportBackup => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    let is_zip = match save_page.get_backup() {
+                        Some(backup) => backup.is_zip(),
+                        None => return Command::none(),
+                    };
+                    let target = if is_zip {
+                        nfd::open_save_dialog(Some("zip"), None)
+                    } else {
+                        nfd::open_pick_folder(None)
+                    };
+                    match target {
+                        Ok(Response::Okay(target)) => {
+                            if let Err(err) = save_page.export_backup(&PathBuf::from(target)) {
+                                return async move { Message::SaveError(err.to_string()) }.into();
+                            }
+                        }
+                        Ok(_) => {}
+                        Err(err) => {
+                            return async move { Message::SaveError(format!("{:?}", err)) }.into()
+                        }
+                    }
+                }
+                Command::none()
+            }
+            Message::LoadSave(
+                smmdb_save,
+                display_name,
+                location,
+                read_only,
+                backup,
+                fingerprint,
+            ) => {
                 self.state = AppState::Default;
                 self.error_state = AppErrorState::None;
                 self.current_page = Page::Save(Box::new(SavePage::new(
                     *smmdb_save.clone(),
                     display_name,
+                    location,
+                    read_only,
+                    backup,
+                    fingerprint,
                     self.smmdb.get_course_responses(),
                 )));
-                let course_ids: Vec<String> = smmdb_save
-                    .get_own_courses()
-                    .iter()
-                    .filter_map(|c| c.as_ref())
-                    .map(|course| {
-                        if let CourseEntry::SavedCourse(course) = &**course {
-                            course.get_course().get_smmdb_id()
-                        } else {
-                            None
-                        }
-                    })
-                    .flatten()
-                    .collect();
+                let course_ids = get_smmdb_ids(&smmdb_save);
                 if course_ids.is_empty() {
                     Command::none()
                 } else {
@@ -234,6 +654,82 @@ impl Application for App {
                     AppErrorState::Some(format!("Could not load save file. Full error:\n{}", err));
                 Command::none()
             }
+            Message::CheckSaveModification => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.check_external_modification();
+                    if !save_page.is_read_only() {
+                        // Scanning all processes is too slow for the UI thread.
+                        let location = save_page.get_location().clone();
+                        return Command::perform(
+                            async move {
+                                let running_emulators = find_running_emulators(&location);
+                                (location, running_emulators)
+                            },
+                            |(location, running_emulators)| {
+                                Message::SetRunningEmulators(location, running_emulators)
+                            },
+                        );
+                    }
+                }
+                Command::none()
+            }
+            Message::SetRunningEmulators(location, running_emulators) => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    if save_page.get_location() == &location {
+                        save_page.set_running_emulators(running_emulators);
+                    }
+                }
+                Command::none()
+            }
+            Message::ReloadSave => {
+                if let Page::Save(ref save_page) = self.current_page {
+                    self.state = AppState::Loading;
+                    let display_name = save_page.get_display_name().clone();
+                    let location = save_page.get_location().clone();
+                    let read_only = save_page.is_read_only();
+                    let backup = save_page.get_backup().cloned();
+                    Command::perform(load_save(location.clone()), move |res| match res {
+                        Ok((smmdb_save, fingerprint)) => Message::LoadSave(
+                            Box::new(smmdb_save),
+                            display_name.clone(),
+                            location.clone(),
+                            read_only,
+                            backup.clone(),
+                            fingerprint,
+                        ),
+                        Err(err) => Message::SaveError(err.to_string()),
+                    })
+                } else {
+                    Command::none()
+                }
+            }
+            Message::MergeSaveModification => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    let fut =
+                        save_page.merge_external_modification(self.smmdb.get_course_responses());
+                    // TODO find better way than block_on
+                    return match futures::executor::block_on(fut) {
+                        Ok(course_ids) if !course_ids.is_empty() => {
+                            async move { Message::FetchSaveCourses(course_ids) }.into()
+                        }
+                        Ok(_) => Command::none(),
+                        Err(err) => async move { Message::SaveError(err.to_string()) }.into(),
+                    };
+                }
+                Command::none()
+            }
+            Message::IgnoreSaveModification => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.ignore_external_modification();
+                }
+                Command::none()
+            }
+            Message::SaveError(err) => {
+                eprintln!("SaveError: {}", &err);
+                self.state = AppState::Default;
+                self.error_state = AppErrorState::Some(err);
+                Command::none()
+            }
             Message::FetchSaveCourses(course_ids) => {
                 let query_params = QueryParams {
                     limit: 120,
@@ -296,24 +792,47 @@ impl Application for App {
                 let course_ids: Vec<String> =
                     self.smmdb.get_course_panels().keys().cloned().collect();
 
-                let mut commands = Vec::<Command<Message>>::new();
-                for id in course_ids {
+                let mut commands = fetch_thumbnails(course_ids);
+                if let Some((query_params, known, generation)) = self.smmdb.update_total_count() {
                     commands.push(Command::perform(
-                        async move {
-                            futures::join!(Smmdb::fetch_thumbnail(id.clone()), async { id })
-                        },
-                        |(thumbnail, id)| {
-                            if let Ok(thumbnail) = thumbnail {
-                                Message::SetSmmdbCourseThumbnail(thumbnail, id)
-                            } else {
-                                // TODO handle error
-                                Message::Empty
-                            }
+                        Smmdb::count(query_params, known, self.settings.apikey.clone()),
+                        move |res| match res {
+                            Ok(count) => Message::SetSmmdbCourseCount(generation, count),
+                            Err(err) => Message::FetchError(err.to_string()),
                         },
                     ));
                 }
                 Command::batch(commands)
             }
+            Message::InfiniteScrollChanged(infinite_scroll) => {
+                self.smmdb.set_infinite_scroll(infinite_scroll);
+                Command::none()
+            }
+            Message::PrefetchCourses => {
+                if let Some((query_params, generation)) = self.smmdb.prepare_prefetch() {
+                    let skip = query_params.skip;
+                    Command::perform(
+                        Smmdb::update(query_params, self.settings.apikey.clone()),
+                        move |res| match res {
+                            Ok(courses) => Message::AppendSmmdbCourses(generation, skip, courses),
+                            Err(err) => Message::FetchError(err.to_string()),
+                        },
+                    )
+                } else {
+                    Command::none()
+                }
+            }
+            Message::AppendSmmdbCourses(generation, skip, courses) => {
+                if let Some(course_ids) = self.smmdb.append_courses(generation, skip, courses) {
+                    Command::batch(fetch_thumbnails(course_ids))
+                } else {
+                    Command::none()
+                }
+            }
+            Message::SetSmmdbCourseCount(generation, count) => {
+                self.smmdb.set_total_count(generation, count);
+                Command::none()
+            }
             Message::SetSelfSmmdbCourses(courses) => {
                 self.state = AppState::Default;
                 self.error_state = AppErrorState::None;
@@ -344,9 +863,154 @@ impl Application for App {
                 Command::none()
             }
             Message::SetSmmdbTab(tab) => {
+                let fetch_favorites = tab == SmmdbTab::Favorites;
                 if let Page::Save(ref mut save_page) = self.current_page {
                     save_page.set_smmdb_tab(tab)
                 }
+                if fetch_favorites {
+                    async { Message::FetchFavorites }.into()
+                } else {
+                    Command::none()
+                }
+            }
+            Message::OpenUploaderProfile(owner, uploader) => {
+                let query_params = self.smmdb.open_profile(owner.clone(), uploader);
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.set_smmdb_tab(SmmdbTab::Profile)
+                }
+                Command::perform(
+                    Smmdb::update_all(query_params, self.settings.apikey.clone()),
+                    move |res| match res {
+                        Ok(courses) => Message::SetUploaderProfileCourses(owner.clone(), courses),
+                        Err(err) => Message::FetchError(err.to_string()),
+                    },
+                )
+            }
+            Message::SetUploaderProfileCourses(owner, courses) => {
+                if let Some(course_ids) = self.smmdb.set_profile_courses(&owner, courses) {
+                    Command::batch(fetch_thumbnails(course_ids))
+                } else {
+                    Command::none()
+                }
+            }
+            Message::CloseUploaderProfile => {
+                self.smmdb.close_profile();
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.set_smmdb_tab(SmmdbTab::Courses)
+                }
+                Command::none()
+            }
+            Message::FollowUploader(owner, uploader) => {
+                self.smmdb.follow(owner.clone(), uploader);
+                self.save_smmdb_settings();
+                Command::perform(
+                    Smmdb::fetch_latest_courses(owner.clone(), self.settings.apikey.clone()),
+                    move |res| match res {
+                        Ok(courses) => Message::SetFollowedUploaderCourses(owner.clone(), courses),
+                        Err(err) => Message::FetchError(err.to_string()),
+                    },
+                )
+            }
+            Message::UnfollowUploader(owner) => {
+                self.smmdb.unfollow(&owner);
+                self.save_smmdb_settings();
+                Command::none()
+            }
+            Message::CheckFollowedUploaders => {
+                let commands = self
+                    .smmdb
+                    .get_followed_uploaders()
+                    .iter()
+                    .map(|followed| {
+                        let owner = followed.owner.clone();
+                        Command::perform(
+                            Smmdb::fetch_latest_courses(
+                                owner.clone(),
+                                self.settings.apikey.clone(),
+                            ),
+                            move |res| match res {
+                                Ok(courses) => {
+                                    Message::SetFollowedUploaderCourses(owner.clone(), courses)
+                                }
+                                Err(err) => Message::FetchError(err.to_string()),
+                            },
+                        )
+                    })
+                    .collect();
+                Command::batch(commands)
+            }
+            Message::SetFollowedUploaderCourses(owner, courses) => {
+                let course_ids = self.smmdb.add_new_courses(&owner, courses);
+                self.save_smmdb_settings();
+                Command::batch(fetch_thumbnails(course_ids))
+            }
+            Message::MarkInboxRead => {
+                self.smmdb.mark_inbox_read();
+                self.save_smmdb_settings();
+                Command::none()
+            }
+            Message::ToggleFavorite(id) => {
+                self.smmdb.toggle_favorite(id);
+                self.save_smmdb_settings();
+                Command::none()
+            }
+            Message::FetchFavorites => {
+                if let Some(query_params) = self.smmdb.get_favorites_query() {
+                    Command::perform(
+                        Smmdb::update_all(query_params, self.settings.apikey.clone()),
+                        move |res| match res {
+                            Ok(courses) => Message::SetFavoriteCourses(courses),
+                            Err(err) => Message::FetchError(err.to_string()),
+                        },
+                    )
+                } else {
+                    Command::none()
+                }
+            }
+            Message::SetFavoriteCourses(courses) => {
+                let course_ids = self.smmdb.set_favorite_courses(courses);
+                Command::batch(fetch_thumbnails(course_ids))
+            }
+            Message::SaveFilterTextChanged(text) => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.get_save_filter_mut().set_text(text);
+                }
+                Command::none()
+            }
+            Message::SaveFilterGameStyleChanged(game_style) => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.get_save_filter_mut().set_game_style(game_style);
+                }
+                Command::none()
+            }
+            Message::SaveFilterThemeChanged(theme) => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.get_save_filter_mut().set_theme(theme);
+                }
+                Command::none()
+            }
+            Message::SaveFilterUploadedChanged(uploaded) => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.get_save_filter_mut().set_uploaded(uploaded);
+                }
+                Command::none()
+            }
+            Message::SaveFilterDifficultyChanged(difficulty) => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.get_save_filter_mut().set_difficulty(difficulty);
+                }
+                Command::none()
+            }
+            Message::SaveFilterSlotChanged(slot) => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.get_save_filter_mut().set_slot(slot);
+                }
+                Command::none()
+            }
+            Message::ResetSaveFilter => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.reset_save_filter();
+                }
                 Command::none()
             }
             Message::InitUploadCourse(course) => {
@@ -387,10 +1051,14 @@ impl Application for App {
                     let mut course = course.get_course().clone();
                     course.set_smmdb_id(id.clone()).unwrap();
                     let fut = save_page.delete_course(index, self.smmdb.get_course_responses());
-                    futures::executor::block_on(fut).unwrap();
+                    if let Err(err) = futures::executor::block_on(fut) {
+                        return async move { Message::SaveError(err.to_string()) }.into();
+                    }
                     let fut =
                         save_page.add_course(index, course, self.smmdb.get_course_responses());
-                    futures::executor::block_on(fut).unwrap();
+                    if let Err(err) = futures::executor::block_on(fut) {
+                        return async move { Message::SaveError(err.to_string()) }.into();
+                    }
                 }
                 Command::batch(vec![
                     async { Message::FetchSaveCourses(vec![id]) }.into(),
@@ -412,18 +1080,57 @@ impl Application for App {
                             second as u8,
                             self.smmdb.get_course_responses(),
                         );
-                        futures::executor::block_on(fut).unwrap();
                         // TODO find better way than block_on
-                        async { Message::ResetState }.into()
+                        match futures::executor::block_on(fut) {
+                            Ok(()) => async { Message::ResetState }.into(),
+                            Err(err) => async move { Message::SaveError(err.to_string()) }.into(),
+                        }
                     }
                     _ => Command::none(),
                 }
             }
+            Message::ExportCourse(index) => {
+                if let Page::Save(ref save_page) = self.current_page {
+                    let res = match nfd::open_save_dialog(Some("zip"), None) {
+                        Ok(Response::Okay(path)) => {
+                            save_page.export_course(index as u8, &PathBuf::from(path))
+                        }
+                        Ok(_) => Ok(()),
+                        Err(err) => Err(anyhow::anyhow!("{:?}", err)),
+                    };
+                    if let Err(err) = res {
+                        return async move { Message::SaveError(err.to_string()) }.into();
+                    }
+                }
+                Command::none()
+            }
             Message::InitDownloadCourse(index) => {
+                if self.is_save_read_only() {
+                    return Command::none();
+                }
                 self.state = AppState::DownloadSelect(index);
                 Command::none()
             }
+            Message::InitDownloadDuplicate(save_index, smmdb_id) => {
+                if self.is_save_read_only() {
+                    return Command::none();
+                }
+                self.state = AppState::DownloadDuplicateSelect {
+                    save_index,
+                    smmdb_id,
+                };
+                Command::none()
+            }
+            Message::JumpToSlot(index) => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.focus_slot(index);
+                }
+                Command::none()
+            }
             Message::DownloadCourse(save_index, smmdb_id) => {
+                if self.is_save_read_only() {
+                    return Command::none();
+                }
                 self.state = AppState::Downloading {
                     save_index,
                     smmdb_id,
@@ -434,8 +1141,8 @@ impl Application for App {
             Message::DownloadProgressed(message) => {
                 if let AppState::Downloading {
                     save_index,
+                    smmdb_id,
                     progress,
-                    ..
                 } = &mut self.state
                 {
                     match message {
@@ -446,17 +1153,60 @@ impl Application for App {
                             *progress = percentage;
                         }
                         Progress::Finished(data) => {
+                            let save_index = *save_index;
+                            let smmdb_id = smmdb_id.clone();
                             match self.current_page {
                                 Page::Save(ref mut save_page) => {
                                     let course: smmdb_lib::Course2 = data.try_into().unwrap();
-                                    let fut = save_page.add_course(
-                                        *save_index as u8,
-                                        course,
-                                        self.smmdb.get_course_responses(),
-                                    );
-                                    futures::executor::block_on(fut).unwrap();
+                                    let index = save_index as u8;
+                                    let course_responses = self.smmdb.get_course_responses();
                                     // TODO find better way than block_on
-                                    return async { Message::ResetState }.into();
+                                    let res = if save_page.has_course(index) {
+                                        // updates replace the outdated course in the same slot
+                                        futures::executor::block_on(save_page.replace_course(
+                                            index,
+                                            course,
+                                            course_responses,
+                                        ))
+                                    } else {
+                                        futures::executor::block_on(save_page.add_course(
+                                            index,
+                                            course,
+                                            course_responses,
+                                        ))
+                                    };
+                                    return match res {
+                                        Ok(()) => {
+                                            let next_update =
+                                                save_page.finish_course_update(save_index);
+                                            self.smmdb.set_installed_version(
+                                                save_page.get_source_location(),
+                                                save_index,
+                                                &smmdb_id,
+                                            );
+                                            self.save_smmdb_settings();
+                                            match next_update {
+                                                // Ends the download first, so that the same
+                                                // course can be downloaded into another slot.
+                                                Some(update) => {
+                                                    self.state = AppState::Default;
+                                                    async move {
+                                                        Message::DownloadCourse(
+                                                            update.slot,
+                                                            update.smmdb_id,
+                                                        )
+                                                    }
+                                                    .into()
+                                                }
+                                                None => async { Message::ResetState }.into(),
+                                            }
+                                        }
+                                        Err(err) => {
+                                            save_page.stop_course_updates();
+                                            async move { Message::SaveError(err.to_string()) }
+                                                .into()
+                                        }
+                                    };
                                 }
                                 _ => {
                                     todo!()
@@ -464,12 +1214,131 @@ impl Application for App {
                             }
                         }
                         Progress::Errored => {
-                            // TODO
+                            let smmdb_id = smmdb_id.clone();
+                            self.state = AppState::Default;
+                            if let Page::Save(ref mut save_page) = self.current_page {
+                                save_page.stop_course_updates();
+                            }
+                            self.error_state = AppErrorState::Some(format!(
+                                "Could not download course {}.",
+                                smmdb_id
+                            ));
                         }
                     }
                 };
                 Command::none()
             }
+            Message::CheckCourseUpdates => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    let course_ids: Vec<String> =
+                        save_page.get_installed_courses().keys().cloned().collect();
+                    if course_ids.is_empty() {
+                        save_page.set_course_updates(vec![]);
+                        return Command::none();
+                    }
+                    save_page.open_course_updates();
+                    let query_params = QueryParams {
+                        limit: 120,
+                        ids: Some(course_ids),
+                        ..QueryParams::default()
+                    };
+                    let apikey = self.settings.apikey.clone();
+                    Command::perform(
+                        Smmdb::update_all(query_params, apikey),
+                        move |res| match res {
+                            Ok(courses) => Message::SetCourseUpdateResponse(courses),
+                            Err(err) => Message::FetchError(err.to_string()),
+                        },
+                    )
+                } else {
+                    Command::none()
+                }
+            }
+            Message::SetCourseUpdateResponse(courses) => {
+                self.smmdb.set_courses(courses.clone(), false);
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    let updates = self.smmdb.find_course_updates(
+                        save_page.get_source_location(),
+                        save_page.get_installed_courses(),
+                        &courses,
+                    );
+                    save_page.set_course_updates(updates);
+                    save_page.set_course_response(self.smmdb.get_course_responses());
+                }
+                Command::none()
+            }
+            Message::UpdateAllCourses => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    if let Some(update) = save_page.start_course_updates() {
+                        return async move {
+                            Message::DownloadCourse(update.slot, update.smmdb_id)
+                        }
+                        .into();
+                    }
+                }
+                Command::none()
+            }
+            Message::CloseCourseUpdates => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    save_page.close_course_updates();
+                }
+                Command::none()
+            }
+            Message::InitEditCourse(index) => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    if let Some(course_panel) = save_page.get_course_panel_mut(index) {
+                        course_panel.init_edit();
+                        self.state = AppState::EditSelect(index);
+                    }
+                }
+                Command::none()
+            }
+            Message::EditCourseTitleChanged(index, title) => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    if let Some(course_panel) = save_page.get_course_panel_mut(index) {
+                        course_panel.set_edit_title(title);
+                    }
+                }
+                Command::none()
+            }
+            Message::EditCourseDescriptionChanged(index, description) => {
+                if let Page::Save(ref mut save_page) = self.current_page {
+                    if let Some(course_panel) = save_page.get_course_panel_mut(index) {
+                        course_panel.set_edit_description(description);
+                    }
+                }
+                Command::none()
+            }
+            Message::EditCourse(index) => match self.current_page {
+                Page::Save(ref mut save_page) => {
+                    let (title, description) = match save_page.get_course_panel_mut(index) {
+                        Some(course_panel) => {
+                            let (title, description) = course_panel.get_edit_values();
+                            (title.clone(), description.clone())
+                        }
+                        None => return Command::none(),
+                    };
+                    self.state = AppState::Loading;
+                    let fut = save_page.edit_course(
+                        index as u8,
+                        &title,
+                        &description,
+                        self.smmdb.get_course_responses(),
+                    );
+                    // TODO find better way than block_on
+                    match futures::executor::block_on(fut) {
+                        Ok(()) => async { Message::ResetState }.into(),
+                        Err(err) => {
+                            self.state = AppState::EditSelect(index);
+                            if let Some(course_panel) = save_page.get_course_panel_mut(index) {
+                                course_panel.set_edit_error(err.to_string());
+                            }
+                            Command::none()
+                        }
+                    }
+                }
+                _ => Command::none(),
+            },
             Message::InitDeleteCourse(index) => {
                 self.state = AppState::DeleteSelect(index);
                 Command::none()
@@ -481,9 +1350,11 @@ impl Application for App {
                     Page::Save(ref mut save_page) => {
                         let fut =
                             save_page.delete_course(index as u8, self.smmdb.get_course_responses());
-                        futures::executor::block_on(fut).unwrap();
                         // TODO find better way than block_on
-                        async { Message::ResetState }.into()
+                        match futures::executor::block_on(fut) {
+                            Ok(()) => async { Message::ResetState }.into(),
+                            Err(err) => async move { Message::SaveError(err.to_string()) }.into(),
+                        }
                     }
                     _ => Command::none(),
                 }
@@ -533,13 +1404,107 @@ impl Application for App {
                 self.smmdb.set_difficulty(difficulty);
                 Command::none()
             }
-            Message::SortChanged(sort) => {
-                self.smmdb.set_sort(sort);
+            Message::ToggleAdvancedSearch => {
+                self.smmdb.toggle_advanced_search();
+                Command::none()
+            }
+            Message::TitleExactChanged(title_exact) => {
+                self.smmdb.set_title_exact(title_exact);
+                Command::none()
+            }
+            Message::TitleCaseSensitiveChanged(title_case_sensitive) => {
+                self.smmdb.set_title_case_sensitive(title_case_sensitive);
+                Command::none()
+            }
+            Message::TitleTrimmedChanged(title_trimmed) => {
+                self.smmdb.set_title_trimmed(title_trimmed);
+                Command::none()
+            }
+            Message::OwnerChanged(owner) => {
+                self.smmdb.set_owner(owner);
+                Command::none()
+            }
+            Message::CourseIdChanged(id) => {
+                self.smmdb.set_id(id);
+                Command::none()
+            }
+            Message::CourseIdsChanged(ids) => {
+                self.smmdb.set_ids(ids);
+                Command::none()
+            }
+            Message::CourseLookupChanged(course_lookup) => {
+                self.smmdb.set_course_lookup(course_lookup);
+                Command::none()
+            }
+            Message::LookupCourse => {
+                if let Err(err) = self.smmdb.apply_course_lookup() {
+                    return async move { Message::FetchError(err.to_string()) }.into();
+                }
+                self.state = AppState::Loading;
+                self.smmdb.reset_pagination();
+                self.save_smmdb_settings();
+                Command::perform(
+                    Smmdb::update(
+                        self.smmdb.get_query_params().clone(),
+                        self.settings.apikey.clone(),
+                    ),
+                    move |res| match res {
+                        Ok(courses) => Message::SetSmmdbCourses(courses),
+                        Err(err) => Message::FetchError(err.to_string()),
+                    },
+                )
+            }
+            Message::SavedSearchSelected(name) => {
+                self.state = AppState::Loading;
+                self.smmdb.load_saved_search(name);
+                self.save_smmdb_settings();
+                Command::perform(
+                    Smmdb::update(
+                        self.smmdb.get_query_params().clone(),
+                        self.settings.apikey.clone(),
+                    ),
+                    move |res| match res {
+                        Ok(courses) => Message::SetSmmdbCourses(courses),
+                        Err(err) => Message::FetchError(err.to_string()),
+                    },
+                )
+            }
+            Message::SavedSearchNameChanged(name) => {
+                self.smmdb.set_saved_search_name(name);
+                Command::none()
+            }
+            Message::SaveSearch => {
+                if let Err(err) = self.smmdb.save_search() {
+                    return async move { Message::FetchError(err.to_string()) }.into();
+                }
+                self.save_smmdb_settings();
+                Command::none()
+            }
+            Message::DeleteSavedSearch => {
+                self.smmdb.delete_saved_search();
+                self.save_smmdb_settings();
+                Command::none()
+            }
+            Message::SortValueChanged(index, val) => {
+                self.smmdb.get_sort_mut().set_value(index, val);
+                Command::none()
+            }
+            Message::SortDirectionChanged(index, direction) => {
+                self.smmdb.get_sort_mut().set_direction(index, direction);
+                Command::none()
+            }
+            Message::AddSortKey => {
+                self.smmdb.get_sort_mut().add_key();
+                Command::none()
+            }
+            Message::RemoveSortKey(index) => {
+                self.smmdb.get_sort_mut().remove_key(index);
                 Command::none()
             }
             Message::ApplyFilters => {
                 self.state = AppState::Loading;
                 self.smmdb.reset_pagination();
+                self.save_smmdb_settings();
                 Command::perform(
                     Smmdb::update(
                         self.smmdb.get_query_params().clone(),
@@ -579,6 +1544,41 @@ impl Application for App {
                     },
                 )
             }
+            Message::PageSizeChanged(limit) => {
+                self.state = AppState::Loading;
+                self.smmdb.set_page_size(limit);
+                self.save_smmdb_settings();
+                Command::perform(
+                    Smmdb::update(
+                        self.smmdb.get_query_params().clone(),
+                        self.settings.apikey.clone(),
+                    ),
+                    move |res| match res {
+                        Ok(courses) => Message::SetSmmdbCourses(courses),
+                        Err(err) => Message::FetchError(err.to_string()),
+                    },
+                )
+            }
+            Message::PageInputChanged(page_input) => {
+                self.smmdb.set_page_input(page_input);
+                Command::none()
+            }
+            Message::JumpToPage => {
+                if let Err(err) = self.smmdb.jump_to_page() {
+                    return async move { Message::FetchError(err.to_string()) }.into();
+                }
+                self.state = AppState::Loading;
+                Command::perform(
+                    Smmdb::update(
+                        self.smmdb.get_query_params().clone(),
+                        self.settings.apikey.clone(),
+                    ),
+                    move |res| match res {
+                        Ok(courses) => Message::SetSmmdbCourses(courses),
+                        Err(err) => Message::FetchError(err.to_string()),
+                    },
+                )
+            }
             Message::PaginateSelfForward => {
                 self.state = AppState::Loading;
                 self.smmdb.self_paginate_forward();
@@ -662,21 +1662,20 @@ impl Application for App {
                 }
                 Command::none()
             }
-            Message::TrySaveSettings(settings) => {
-                settings.save().unwrap();
-                match &settings.apikey {
-                    Some(apikey) => {
-                        Command::perform(Smmdb::try_sign_in(apikey.clone()), move |res| match res {
-                            Ok(user) => Message::SaveSettings((settings.clone(), Some(user))),
-                            Err(err) => Message::RejectSettings(err),
-                        })
-                    }
-                    None => async move { Message::SaveSettings((settings.clone(), None)) }.into(),
+            Message::TrySaveSettings(apikey) => match apikey {
+                Some(apikey) => {
+                    Command::perform(Smmdb::try_sign_in(apikey.clone()), move |res| match res {
+                        Ok(user) => Message::SaveSettings((Some(apikey.clone()), Some(user))),
+                        Err(err) => Message::RejectSettings(err),
+                    })
                 }
-            }
-            Message::SaveSettings((settings, user)) => {
-                settings.save().unwrap();
-                self.settings = settings;
+                None => async move { Message::SaveSettings((None, None)) }.into(),
+            },
+            Message::SaveSettings((apikey, user)) => {
+                // only the API key is edited on the settings page, everything else might have
+                // changed since the page has been opened
+                self.settings.apikey = apikey;
+                self.settings.save().unwrap();
                 self.smmdb.set_user(user);
                 if let Page::Settings(ref mut settings_page) = self.current_page {
                     self.current_page = settings_page.get_prev_page()
@@ -719,10 +1718,12 @@ impl Application for App {
     }
 
     fn subscription(&self) -> Subscription<Message> {
-        match &self.state {
+        let state_subscription = match &self.state {
             AppState::UploadSelect(_)
             | AppState::SwapSelect(_)
             | AppState::DownloadSelect(_)
+            | AppState::DownloadDuplicateSelect { .. }
+            | AppState::EditSelect(_)
             | AppState::DeleteSelect(_)
             | AppState::DeleteSmmdbSelect(_) => subscription::events().map(|event| match event {
                 Event::Keyboard(keyboard::Event::KeyReleased {
@@ -735,7 +1736,17 @@ impl Application for App {
                 Smmdb::download_course(smmdb_id.clone()).map(Message::DownloadProgressed)
             }
             AppState::Default | AppState::Loading => Subscription::none(),
+        };
+        let mut subscriptions = vec![
+            state_subscription,
+            iced::time::every(FOLLOW_CHECK_INTERVAL).map(|_| Message::CheckFollowedUploaders),
+        ];
+        if let Page::Save(_) = self.current_page {
+            subscriptions.push(
+                iced::time::every(Duration::from_secs(2)).map(|_| Message::CheckSaveModification),
+            );
         }
+        Subscription::batch(subscriptions)
     }
 
     fn view(&mut self) -> Element<Self::Message> {
@@ -759,7 +1770,9 @@ impl Application for App {
                 )
                 .push(match &mut self.current_page {
                     Page::Init(init_page) => init_page.view(&self.state, &self.error_state),
-                    Page::Save(save_page) => save_page.view(&self.state, &mut self.smmdb),
+                    Page::Save(save_page) => {
+                        save_page.view(&self.state, &self.error_state, &mut self.smmdb)
+                    }
                     Page::Settings(settings_page) => settings_page.view(&self.error_state),
                 }),
         )
@@ -770,6 +1783,124 @@ impl Application for App {
     }
 }
 
+impl App {
+    /// Persists the last query, the saved searches, the followed uploaders, the favorites and the
+    /// versions of installed courses.
+    fn save_smmdb_settings(&mut self) {
+        let mut query_params = self.smmdb.get_query_params().clone();
+        query_params.skip = 0;
+        // Course lookups are not restored, since they only ever show a single course.
+        query_params.id = None;
+        self.settings.query_params = Some(query_params);
+        self.settings.saved_searches = self.smmdb.get_saved_searches().clone();
+        self.settings.followed_uploaders = self.smmdb.get_followed_uploaders().clone();
+        self.settings.favorites = self.smmdb.get_favorites().clone();
+        self.settings.installed_course_versions = self.smmdb.get_installed_versions().clone();
+        if let Err(err) = self.settings.save() {
+            self.error_state = AppErrorState::Some(err.to_string());
+        }
+    }
+
+    /// Persists changes to search roots or registered saves and updates the init page.
+    fn save_init_settings(&mut self, rescan: bool) -> Command<Message> {
+        if let Err(err) = self.settings.save() {
+            self.error_state = AppErrorState::Some(err.to_string());
+        }
+        if let Page::Init(ref mut init_page) = self.current_page {
+            init_page.set_settings(&self.settings);
+            if rescan {
+                let generation = init_page.start_scan();
+                return scan_emu_step(
+                    generation,
+                    ScanStep::ALL[0],
+                    self.settings.search_roots.clone(),
+                );
+            }
+        }
+        Command::none()
+    }
+
+    /// Downloads are refused for read-only saves, since they would write the save.
+    fn is_save_read_only(&self) -> bool {
+        if let Page::Save(ref save_page) = self.current_page {
+            save_page.is_read_only()
+        } else {
+            false
+        }
+    }
+
+    fn open_save(&mut self, save: EmuSave, read_only: bool) -> Command<Message> {
+        self.state = AppState::Loading;
+        let display_name = save.get_display_name().clone();
+        let location = save.get_location().clone();
+        Command::perform(load_save(location.clone()), move |res| match res {
+            Ok((smmdb_save, fingerprint)) => Message::LoadSave(
+                Box::new(smmdb_save),
+                display_name.clone(),
+                location.clone(),
+                read_only,
+                None,
+                fingerprint,
+            ),
+            Err(err) => Message::LoadSaveError(err.to_string()),
+        })
+    }
+
+    fn is_read_only_requested(&self) -> bool {
+        if let Page::Init(ref init_page) = self.current_page {
+            init_page.is_read_only()
+        } else {
+            false
+        }
+    }
+}
+
+fn get_smmdb_ids(smmdb_save: &smmdb_lib::Save) -> Vec<String> {
+    smmdb_save
+        .get_own_courses()
+        .iter()
+        .filter_map(|c| c.as_ref())
+        .map(|course| {
+            if let CourseEntry::SavedCourse(course) = &**course {
+                course.get_course().get_smmdb_id()
+            } else {
+                None
+            }
+        })
+        .flatten()
+        .collect()
+}
+
+fn fetch_thumbnails(course_ids: Vec<String>) -> Vec<Command<Message>> {
+    course_ids
+        .into_iter()
+        .map(|id| {
+            Command::perform(
+                async move { futures::join!(Smmdb::fetch_thumbnail(id.clone()), async { id }) },
+                |(thumbnail, id)| {
+                    if let Ok(thumbnail) = thumbnail {
+                        Message::SetSmmdbCourseThumbnail(thumbnail, id)
+                    } else {
+                        // TODO handle error
+                        Message::Empty
+                    }
+                },
+            )
+        })
+        .collect()
+}
+
+fn scan_emu_step(
+    generation: usize,
+    step: ScanStep,
+    search_roots: Vec<PathBuf>,
+) -> Command<Message> {
+    Command::perform(
+        async move { scan_emu_dirs(step, &search_roots) },
+        move |scan| Message::EmuScanned(generation, step, scan),
+    )
+}
+
 struct AppStyle;
 
 impl container::StyleSheet for AppStyle {
@@ -0,0 +1,255 @@
+use anyhow::{anyhow, Result};
+use std::{
+    env,
+    fmt::{self, Display},
+    fs::{self, File},
+    io::{self, Cursor, Read, Seek, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tempfile::TempDir;
+use zip::{write::FileOptions, ZipArchive, ZipWriter};
+
+/// The homebrew tool which created a save backup.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BackupFormat {
+    Jksv,
+    Checkpoint,
+    Unknown,
+}
+
+impl Display for BackupFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BackupFormat::Jksv => write!(f, "JKSV"),
+            BackupFormat::Checkpoint => write!(f, "Checkpoint"),
+            BackupFormat::Unknown => write!(f, "Backup"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum BackupLayout {
+    /// Folder containing the save files. The name of the folder is used to name exported backups.
+    Folder { name: String },
+    /// Zip archive. Files are stored below the given prefix, which is empty if the save files are
+    /// stored at the root of the archive.
+    Zip { prefix: String },
+}
+
+/// A save backup of a Switch console.
+///
+/// The backup is copied into a working directory, so that it can be edited like an emulator save
+/// without touching the original backup. Changes are written back by exporting the backup.
+///
+/// The working directory is removed, once the backup and all of its clones have been dropped.
+#[derive(Clone, Debug)]
+pub struct SaveBackup {
+    source: PathBuf,
+    format: BackupFormat,
+    layout: BackupLayout,
+    working_dir: PathBuf,
+    _temp_dir: Arc<TempDir>,
+}
+
+impl SaveBackup {
+    /// Opens a JKSV or Checkpoint backup, which can either be a folder or a zip archive.
+    pub fn open(source: PathBuf) -> Result<SaveBackup> {
+        let format = detect_format(&source);
+        let temp_dir = create_working_dir()?;
+        let working_dir = temp_dir.path().to_path_buf();
+        let layout = if source.is_dir() {
+            let save_dir = find_save_dir(&source).ok_or_else(|| {
+                anyhow!(
+                    "No save.dat found in {}. Please select the folder of a single backup.",
+                    source.to_string_lossy()
+                )
+            })?;
+            copy_dir(&save_dir, &working_dir)?;
+            BackupLayout::Folder {
+                name: save_dir
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "backup".to_string()),
+            }
+        } else {
+            extract_zip(&source, &working_dir)?
+        };
+        Ok(SaveBackup {
+            source,
+            format,
+            layout,
+            working_dir,
+            _temp_dir: Arc::new(temp_dir),
+        })
+    }
+
+    pub fn get_source(&self) -> &PathBuf {
+        &self.source
+    }
+
+    pub fn get_format(&self) -> BackupFormat {
+        self.format
+    }
+
+    /// The folder which contains the editable save files.
+    pub fn get_working_dir(&self) -> &PathBuf {
+        &self.working_dir
+    }
+
+    pub fn is_zip(&self) -> bool {
+        matches!(self.layout, BackupLayout::Zip { .. })
+    }
+
+    pub fn get_display_name(&self) -> String {
+        format!("[{}] {}", self.format, self.source.to_string_lossy())
+    }
+
+    /// Writes the edited save in the layout of the original backup.
+    ///
+    /// Folder backups are exported as a new folder inside of `target`, so that the tool lists it
+    /// next to the original backup. Zip backups are written to `target`, keeping all entries of
+    /// the original archive outside of the save folder.
+    pub fn export(&self, target: &Path) -> Result<PathBuf> {
+        match &self.layout {
+            BackupLayout::Folder { name } => {
+                let target = target.join(format!("{} (SMMDB)", name));
+                if target.exists() {
+                    return Err(anyhow!("{} already exists", target.to_string_lossy()));
+                }
+                copy_dir(&self.working_dir, &target)?;
+                Ok(target)
+            }
+            BackupLayout::Zip { prefix } => {
+                // The archive is built in memory, since the original backup might be overwritten.
+                let mut zip = ZipWriter::new(Cursor::new(vec![]));
+                write_zip_dir(&mut zip, &self.working_dir, prefix)?;
+                let mut archive = ZipArchive::new(File::open(&self.source)?)?;
+                for index in 0..archive.len() {
+                    let file = archive.by_index(index)?;
+                    if !file.name().starts_with(prefix.as_str()) {
+                        zip.raw_copy_file(file)?;
+                    }
+                }
+                let buffer = zip.finish()?.into_inner();
+                fs::write(target, buffer)?;
+                Ok(target.to_path_buf())
+            }
+        }
+    }
+}
+
+/// JKSV stores backups in `JKSV/<title name>`, Checkpoint in `Checkpoint/saves/<title id> <title
+/// name>`.
+fn detect_format(source: &Path) -> BackupFormat {
+    let is_format = |name: &str| {
+        source.components().any(|component| {
+            component
+                .as_os_str()
+                .to_string_lossy()
+                .eq_ignore_ascii_case(name)
+        })
+    };
+    if is_format("JKSV") {
+        BackupFormat::Jksv
+    } else if is_format("Checkpoint") {
+        BackupFormat::Checkpoint
+    } else {
+        BackupFormat::Unknown
+    }
+}
+
+/// Backups either contain the save files directly or, if the title folder has been selected, a
+/// single backup folder.
+fn find_save_dir(source: &Path) -> Option<PathBuf> {
+    if source.join("save.dat").is_file() {
+        return Some(source.to_path_buf());
+    }
+    let mut save_dirs = fs::read_dir(source)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.join("save.dat").is_file());
+    match (save_dirs.next(), save_dirs.next()) {
+        (Some(save_dir), None) => Some(save_dir),
+        _ => None,
+    }
+}
+
+fn create_working_dir() -> Result<TempDir> {
+    let root = env::temp_dir().join("smmdb");
+    fs::create_dir_all(&root)?;
+    Ok(tempfile::Builder::new()
+        .prefix("backup-")
+        .tempdir_in(root)?)
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let path = entry?.path();
+        let target = to.join(path.file_name().unwrap_or_default());
+        if path.is_dir() {
+            copy_dir(&path, &target)?;
+        } else {
+            fs::copy(&path, &target)?;
+        }
+    }
+    Ok(())
+}
+
+fn extract_zip(source: &Path, working_dir: &Path) -> Result<BackupLayout> {
+    let mut archive = ZipArchive::new(File::open(source)?)?;
+    let save_dat = (0..archive.len())
+        .filter_map(|index| {
+            archive
+                .by_index(index)
+                .ok()
+                .map(|file| file.name().to_string())
+        })
+        .find(|name| name == "save.dat" || name.ends_with("/save.dat"))
+        .ok_or_else(|| anyhow!("No save.dat found in {}", source.to_string_lossy()))?;
+    let prefix = save_dat.trim_end_matches("save.dat").to_string();
+
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index)?;
+        let path = match file.enclosed_name() {
+            Some(path) => path.to_path_buf(),
+            None => continue,
+        };
+        let path = match path.strip_prefix(&prefix) {
+            Ok(path) => working_dir.join(path),
+            Err(_) => continue,
+        };
+        if file.is_dir() {
+            fs::create_dir_all(&path)?;
+        } else {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            io::copy(&mut file, &mut File::create(&path)?)?;
+        }
+    }
+    Ok(BackupLayout::Zip { prefix })
+}
+
+fn write_zip_dir<W: Write + Seek>(zip: &mut ZipWriter<W>, dir: &Path, prefix: &str) -> Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let name = format!(
+            "{}{}",
+            prefix,
+            path.file_name().unwrap_or_default().to_string_lossy()
+        );
+        if path.is_dir() {
+            zip.add_directory(name.clone(), FileOptions::default())?;
+            write_zip_dir(zip, &path, &format!("{}/", name))?;
+        } else {
+            let mut buffer = vec![];
+            File::open(&path)?.read_to_end(&mut buffer)?;
+            zip.start_file(name, FileOptions::default())?;
+            zip.write_all(&buffer)?;
+        }
+    }
+    Ok(())
+}
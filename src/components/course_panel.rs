@@ -1,15 +1,17 @@
 use crate::{
     components::VotingPanel,
+    course_edit::{self, DESCRIPTION_MAX_LENGTH, TITLE_MAX_LENGTH},
     font::*,
     icon,
+    save_filter::SaveFilter,
     smmdb::{Course2Response, SmmdbUser},
     styles::*,
     AppState, Message,
 };
 
 use iced::{
-    button, container, image, Align, Button, Color, Column, Container, Element, Image, Length,
-    ProgressBar, Row, Space, Text,
+    button, container, image, text_input, Align, Button, Color, Column, Container, Element, Image,
+    Length, ProgressBar, Row, Space, Text, TextInput,
 };
 use smmdb_lib::CourseEntry;
 
@@ -20,11 +22,19 @@ pub struct CoursePanel {
     add_state: button::State,
     upload_state: button::State,
     swap_state: button::State,
+    edit_state: button::State,
+    edit_title_state: text_input::State,
+    edit_description_state: text_input::State,
+    edit_save_state: button::State,
     delete_state: button::State,
     delete_confirm_state: button::State,
     delete_cancel_state: button::State,
+    export_state: button::State,
     course: Option<Box<CourseEntry>>,
     course_response: Option<Course2Response>,
+    edit_title: String,
+    edit_description: String,
+    edit_error: Option<String>,
 }
 
 impl CoursePanel {
@@ -38,11 +48,19 @@ impl CoursePanel {
             add_state: button::State::new(),
             upload_state: button::State::new(),
             swap_state: button::State::new(),
+            edit_state: button::State::new(),
+            edit_title_state: text_input::State::new(),
+            edit_description_state: text_input::State::new(),
+            edit_save_state: button::State::new(),
             delete_state: button::State::new(),
             delete_confirm_state: button::State::new(),
             delete_cancel_state: button::State::new(),
+            export_state: button::State::new(),
             course,
             course_response,
+            edit_title: String::new(),
+            edit_description: String::new(),
+            edit_error: None,
         }
     }
 
@@ -66,11 +84,52 @@ impl CoursePanel {
         self.course_response = None;
     }
 
+    pub fn init_edit(&mut self) {
+        if let Some(course) = &self.course {
+            if let CourseEntry::SavedCourse(course) = &**course {
+                let course_header = course.get_course().get_course().get_header();
+                self.edit_title = course_header.get_title().to_string();
+                self.edit_description = course_header.get_description().to_string();
+                self.edit_error = None;
+            }
+        }
+    }
+
+    pub fn set_edit_title(&mut self, title: String) {
+        self.edit_title = title;
+        self.validate_edit();
+    }
+
+    pub fn set_edit_description(&mut self, description: String) {
+        self.edit_description = description;
+        self.validate_edit();
+    }
+
+    /// Validates both edited values, so that an error of one field is not hidden by the other.
+    fn validate_edit(&mut self) {
+        self.edit_error = course_edit::validate_title(&self.edit_title)
+            .and_then(|_| course_edit::validate_description(&self.edit_description))
+            .err();
+    }
+
+    pub fn set_edit_error(&mut self, err: String) {
+        self.edit_error = Some(err);
+    }
+
+    pub fn get_edit_values(&self) -> (&String, &String) {
+        (&self.edit_title, &self.edit_description)
+    }
+
+    pub fn matches(&self, filter: &SaveFilter) -> bool {
+        filter.matches(self.course.as_deref(), self.course_response.as_ref())
+    }
+
     pub fn view(
         &mut self,
         state: &AppState,
         index: usize,
         smmdb_user: Option<&SmmdbUser>,
+        read_only: bool,
     ) -> impl Into<Element<Message>> {
         let content: Element<Message> = if let Some(course) = &self.course {
             match &**course {
@@ -114,6 +173,81 @@ impl CoursePanel {
                         .width(Length::Shrink);
 
                     content = match state {
+                        AppState::EditSelect(idx) if *idx == index => {
+                            let mut edit_content = Column::new()
+                                .push(Space::with_height(Length::Units(18)))
+                                .push(
+                                    Text::new(format!(
+                                        "Title (max. {} characters):",
+                                        TITLE_MAX_LENGTH
+                                    ))
+                                    .size(16)
+                                    .font(HELVETICA_BOLD),
+                                )
+                                .push(
+                                    TextInput::new(
+                                        &mut self.edit_title_state,
+                                        "Title",
+                                        &self.edit_title,
+                                        move |title| Message::EditCourseTitleChanged(index, title),
+                                    )
+                                    .style(DefaultTextInputStyle)
+                                    .padding(4),
+                                )
+                                .push(Space::with_height(Length::Units(8)))
+                                .push(
+                                    Text::new(format!(
+                                        "Description (max. {} characters):",
+                                        DESCRIPTION_MAX_LENGTH
+                                    ))
+                                    .size(16)
+                                    .font(HELVETICA_BOLD),
+                                )
+                                .push(
+                                    TextInput::new(
+                                        &mut self.edit_description_state,
+                                        "Description",
+                                        &self.edit_description,
+                                        move |description| {
+                                            Message::EditCourseDescriptionChanged(
+                                                index,
+                                                description,
+                                            )
+                                        },
+                                    )
+                                    .style(DefaultTextInputStyle)
+                                    .padding(4),
+                                );
+                            if let Some(err) = &self.edit_error {
+                                edit_content = edit_content
+                                    .push(Space::with_height(Length::Units(8)))
+                                    .push(Text::new(err).size(16).color(TEXT_DANGER_COLOR));
+                            }
+                            let mut save_button = Button::new(
+                                &mut self.edit_save_state,
+                                Text::new("Save").size(20).font(HELVETICA_BOLD),
+                            )
+                            .padding(BUTTON_PADDING)
+                            .style(UploadButtonStyle);
+                            if self.edit_error.is_none() {
+                                save_button = save_button.on_press(Message::EditCourse(index));
+                            }
+                            content.push(edit_content).push(
+                                Row::new()
+                                    .push(Space::with_width(Length::Fill))
+                                    .push(
+                                        Button::new(
+                                            &mut self.delete_cancel_state,
+                                            Text::new("Cancel").size(20).font(HELVETICA_BOLD),
+                                        )
+                                        .padding(BUTTON_PADDING)
+                                        .style(DefaultButtonStyle)
+                                        .on_press(Message::ResetState),
+                                    )
+                                    .push(Space::with_width(Length::Units(16)))
+                                    .push(save_button),
+                            )
+                        }
                         AppState::DeleteSelect(idx) if *idx == index => content
                             .push(Space::with_height(Length::Units(18)))
                             .push(
@@ -250,6 +384,26 @@ impl CoursePanel {
                         _ => swap_button.on_press(Message::InitSwapCourse(index)),
                     };
 
+                    let mut edit_button = Button::new(
+                        &mut self.edit_state,
+                        icon::EDIT
+                            .clone()
+                            .width(Length::Units(24))
+                            .height(Length::Units(24)),
+                    )
+                    .style(DefaultButtonStyle);
+                    edit_button = match state {
+                        AppState::EditSelect(idx) => {
+                            if *idx == index {
+                                edit_button.on_press(Message::ResetState)
+                            } else {
+                                edit_button.on_press(Message::InitEditCourse(index))
+                            }
+                        }
+                        AppState::Loading | AppState::Downloading { .. } => edit_button,
+                        _ => edit_button.on_press(Message::InitEditCourse(index)),
+                    };
+
                     let mut delete_button = Button::new(
                         &mut self.delete_state,
                         icon::DELETE
@@ -270,7 +424,15 @@ impl CoursePanel {
                         _ => delete_button.on_press(Message::InitDeleteCourse(index)),
                     };
 
-                    if smmdb_user.is_some()
+                    let mut export_button =
+                        Button::new(&mut self.export_state, Text::new("Export").size(14))
+                            .style(DefaultButtonStyle);
+                    if state != &AppState::Loading {
+                        export_button = export_button.on_press(Message::ExportCourse(index));
+                    }
+
+                    if !read_only
+                        && smmdb_user.is_some()
                         && self.course_response.is_none()
                         && state != &AppState::Loading
                     {
@@ -288,16 +450,23 @@ impl CoursePanel {
                             .push(Space::with_height(Length::Units(10)));
                     }
 
-                    actions = actions
-                        .push(swap_button)
-                        .push(Space::with_height(Length::Units(10)))
-                        .push(delete_button);
+                    if !read_only {
+                        actions = actions
+                            .push(swap_button)
+                            .push(Space::with_height(Length::Units(10)))
+                            .push(edit_button)
+                            .push(Space::with_height(Length::Units(10)))
+                            .push(delete_button)
+                            .push(Space::with_height(Length::Units(10)));
+                    }
+                    // Exporting never writes the save, so it stays available in read-only mode.
+                    actions = actions.push(export_button);
                 }
                 CourseEntry::CorruptedCourse(_) => {
                     todo!();
                 }
             }
-        } else {
+        } else if !read_only {
             let mut download_button = Button::new(
                 &mut self.add_state,
                 icon::ADD
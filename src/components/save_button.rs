@@ -1,24 +1,71 @@
-use crate::{styles::*, AppState, EmuSave, EmuType, Message};
+use crate::{
+    emu::UserProfile, icon, settings::RegisteredSave, styles::*, AppState, EmuSave, EmuType,
+    Message,
+};
 
-use iced::{button, Button, Element, Text};
+use iced::{button, image, Align, Button, Element, Image, Length, Row, Space, Text};
 use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
 pub struct SaveButton {
     display_name: String,
     state: button::State,
+    remove_state: Option<button::State>,
+    avatar: Option<image::Handle>,
     save: EmuSave,
 }
 
 impl SaveButton {
-    pub fn new(display_name: String, location: PathBuf, emu_type: EmuType) -> SaveButton {
+    pub fn new(
+        display_name: String,
+        location: PathBuf,
+        emu_type: EmuType,
+        profile: Option<UserProfile>,
+    ) -> SaveButton {
+        let mut save = EmuSave::new(display_name.clone(), location, Some(emu_type));
+        save.set_profile(profile);
+        SaveButton {
+            display_name,
+            state: button::State::new(),
+            remove_state: None,
+            avatar: get_avatar(&save),
+            save,
+        }
+    }
+
+    pub fn from_save(save: EmuSave) -> SaveButton {
+        SaveButton {
+            display_name: save.to_string(),
+            state: button::State::new(),
+            remove_state: None,
+            avatar: get_avatar(&save),
+            save,
+        }
+    }
+
+    /// Creates a button for a save which has been registered by the user and can be removed.
+    pub fn from_registered(registered_save: &RegisteredSave) -> SaveButton {
+        let display_name = format!(
+            "[{}] {}",
+            registered_save.emu_type, registered_save.display_name
+        );
         SaveButton {
             display_name: display_name.clone(),
             state: button::State::new(),
-            save: EmuSave::new(display_name, location, emu_type),
+            remove_state: Some(button::State::new()),
+            avatar: None,
+            save: EmuSave::new(
+                display_name,
+                registered_save.location.clone(),
+                Some(registered_save.emu_type),
+            ),
         }
     }
 
+    pub fn get_location(&self) -> &PathBuf {
+        self.save.get_location()
+    }
+
     pub fn view(&mut self, state: &AppState) -> impl Into<Element<Message>> {
         let mut save_button = Button::new(&mut self.state, Text::new(&self.display_name))
             .padding(BUTTON_PADDING)
@@ -27,10 +74,42 @@ impl SaveButton {
             AppState::Loading => save_button,
             _ => save_button.on_press(Message::OpenSave(self.save.clone())),
         };
-        save_button
+        let mut content = Row::new().align_items(Align::Center);
+        if let Some(avatar) = &self.avatar {
+            content = content
+                .push(
+                    Image::new(avatar.clone())
+                        .width(Length::Units(32))
+                        .height(Length::Units(32)),
+                )
+                .push(Space::with_width(Length::Units(10)));
+        }
+        content = content.push(save_button);
+        if let Some(remove_state) = &mut self.remove_state {
+            content = content.push(Space::with_width(Length::Units(10))).push(
+                Button::new(
+                    remove_state,
+                    icon::DELETE
+                        .clone()
+                        .width(Length::Units(24))
+                        .height(Length::Units(24)),
+                )
+                .style(DeleteButtonStyle)
+                .on_press(Message::RemoveRegisteredSave(
+                    self.save.get_location().clone(),
+                )),
+            );
+        }
+        content
     }
 }
 
+fn get_avatar(save: &EmuSave) -> Option<image::Handle> {
+    save.get_profile()
+        .and_then(|profile| profile.avatar.clone())
+        .map(image::Handle::from_memory)
+}
+
 struct SaveButtonStyle;
 
 impl button::StyleSheet for SaveButtonStyle {
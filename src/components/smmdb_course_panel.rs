@@ -17,8 +17,13 @@ use iced_native::widget::image::Handle;
 pub struct SmmdbCoursePanel {
     voting_panel: VotingPanel,
     panel_state: button::State,
+    uploader_state: button::State,
+    jump_state: button::State,
+    download_confirm_state: button::State,
+    download_cancel_state: button::State,
     upvote_state: button::State,
     downvote_state: button::State,
+    favorite_state: button::State,
     delete_state: button::State,
     delete_confirm_state: button::State,
     delete_cancel_state: button::State,
@@ -31,8 +36,13 @@ impl SmmdbCoursePanel {
         SmmdbCoursePanel {
             voting_panel: VotingPanel::new(),
             panel_state: button::State::new(),
+            uploader_state: button::State::new(),
+            jump_state: button::State::new(),
+            download_confirm_state: button::State::new(),
+            download_cancel_state: button::State::new(),
             upvote_state: button::State::new(),
             downvote_state: button::State::new(),
+            favorite_state: button::State::new(),
             delete_state: button::State::new(),
             delete_confirm_state: button::State::new(),
             delete_cancel_state: button::State::new(),
@@ -49,6 +59,8 @@ impl SmmdbCoursePanel {
         &mut self,
         state: &AppState,
         smmdb_user: Option<&SmmdbUser>,
+        is_favorite: bool,
+        installed_slots: &[usize],
     ) -> impl Into<Element<Message>> {
         let course = self.course.get_course();
         let course_header = course.get_header();
@@ -109,8 +121,49 @@ impl SmmdbCoursePanel {
             )
             .align_items(Align::Center);
 
+        let mut uploader_button = Button::new(
+            &mut self.uploader_state,
+            Text::new(format!("by {}", self.course.get_uploader())).size(15),
+        )
+        .padding(0)
+        .style(UploaderButtonStyle);
+        // The whole panel is a button while selecting a course to download.
+        if !matches!(state, AppState::DownloadSelect(_)) {
+            uploader_button = uploader_button.on_press(Message::OpenUploaderProfile(
+                self.course.get_owner().clone(),
+                self.course.get_uploader().clone(),
+            ));
+        }
+
+        let installed_text = installed_slots
+            .iter()
+            .map(|slot| (slot + 1).to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
         let mut content = Column::new()
             .push(Text::new(course_header.get_title()).size(24))
+            .push(uploader_button);
+        if let Some(slot) = installed_slots.first() {
+            let mut jump_button =
+                Button::new(&mut self.jump_state, Text::new("Jump to slot").size(14))
+                    .style(DefaultButtonStyle);
+            if !matches!(state, AppState::DownloadSelect(_)) {
+                jump_button = jump_button.on_press(Message::JumpToSlot(*slot));
+            }
+            content = content.push(
+                Row::new()
+                    .spacing(LIST_SPACING)
+                    .align_items(Align::Center)
+                    .push(
+                        Text::new(format!("Installed in slot {}", installed_text))
+                            .size(15)
+                            .color(TEXT_HIGHLIGHT_COLOR),
+                    )
+                    .push(jump_button),
+            );
+        }
+        content = content
             .push(Space::with_height(Length::Units(10)))
             .push(inner_content);
 
@@ -145,6 +198,42 @@ impl SmmdbCoursePanel {
                             .on_press(Message::DeleteSmmdbCourse(course_id.clone())),
                         ),
                 ),
+            AppState::DownloadDuplicateSelect {
+                save_index,
+                smmdb_id,
+            } if smmdb_id == course_id => content
+                .push(Space::with_height(Length::Units(18)))
+                .push(
+                    Text::new(format!(
+                        "This course is already installed in slot {}. Do you want to download it again?",
+                        installed_text
+                    ))
+                    .size(16)
+                    .font(HELVETICA_BOLD),
+                )
+                .push(
+                    Row::new()
+                        .push(Space::with_width(Length::Fill))
+                        .push(
+                            Button::new(
+                                &mut self.download_cancel_state,
+                                Text::new("Cancel").size(20).font(HELVETICA_BOLD),
+                            )
+                            .padding(BUTTON_PADDING)
+                            .style(DefaultButtonStyle)
+                            .on_press(Message::ResetState),
+                        )
+                        .push(Space::with_width(Length::Units(16)))
+                        .push(
+                            Button::new(
+                                &mut self.download_confirm_state,
+                                Text::new("Download").size(20).font(HELVETICA_BOLD),
+                            )
+                            .padding(BUTTON_PADDING)
+                            .style(DefaultButtonStyle)
+                            .on_press(Message::DownloadCourse(*save_index, course_id.clone())),
+                        ),
+                ),
             _ => content,
         };
 
@@ -153,10 +242,12 @@ impl SmmdbCoursePanel {
                 .style(SmmdbCoursePanelButtonStyle(state.clone()))
                 .padding(12)
                 .width(Length::Fill)
-                .on_press(Message::DownloadCourse(
-                    *index,
-                    self.course.get_id().clone(),
-                ))
+                // Downloading into the slot which already contains the course is an update.
+                .on_press(if installed_slots.iter().any(|slot| slot != index) {
+                    Message::InitDownloadDuplicate(*index, course_id.clone())
+                } else {
+                    Message::DownloadCourse(*index, course_id.clone())
+                })
                 .into(),
             _ => Container::new(content)
                 .style(SmmdbCoursePanelStyle)
@@ -165,7 +256,19 @@ impl SmmdbCoursePanel {
                 .into(),
         };
 
-        let mut actions = Column::new();
+        let mut actions = Column::new().spacing(LIST_SPACING).push(
+            Button::new(
+                &mut self.favorite_state,
+                Text::new(if is_favorite {
+                    "Unfavorite"
+                } else {
+                    "Favorite"
+                })
+                .size(14),
+            )
+            .style(DefaultButtonStyle)
+            .on_press(Message::ToggleFavorite(course_id.clone())),
+        );
 
         if let Some(smmdb_user) = smmdb_user {
             if self.course.get_owner() == &smmdb_user.id {
@@ -204,6 +307,10 @@ impl SmmdbCoursePanel {
         self.course.get_id()
     }
 
+    pub fn get_course(&self) -> &Course2Response {
+        &self.course
+    }
+
     pub fn set_thumbnail(&mut self, thumbnail: Vec<u8>) {
         self.thumbnail = Some(thumbnail);
     }
@@ -237,6 +344,24 @@ impl button::StyleSheet for SmmdbCoursePanelButtonStyle {
     }
 }
 
+struct UploaderButtonStyle;
+
+impl button::StyleSheet for UploaderButtonStyle {
+    fn active(&self) -> button::Style {
+        button::Style {
+            text_color: TEXT_HIGHLIGHT_COLOR,
+            ..button::Style::default()
+        }
+    }
+
+    fn hovered(&self) -> button::Style {
+        button::Style {
+            text_color: Color::BLACK,
+            ..button::Style::default()
+        }
+    }
+}
+
 struct SmmdbCoursePanelStyle;
 
 impl container::StyleSheet for SmmdbCoursePanelStyle {
@@ -0,0 +1,238 @@
+use anyhow::{anyhow, Result};
+use smmdb_lib::Course2;
+
+/// Maximum amount of characters for a course title as allowed by the game.
+pub const TITLE_MAX_LENGTH: usize = 32;
+/// Maximum amount of characters for a course description as allowed by the game.
+pub const DESCRIPTION_MAX_LENGTH: usize = 75;
+
+/// The course data returned by `smmdb_lib` is a whole decrypted course file. It starts with a file
+/// header containing the checksum, which is followed by the course header and the course areas.
+const FILE_HEADER_SIZE: usize = 0x10;
+const CHECKSUM_OFFSET: usize = 0x08;
+/// The checksum covers everything after the file header up to the encryption info at the end.
+const CHECKSUM_DATA_END: usize = 0x5BFD0;
+const TITLE_OFFSET: usize = FILE_HEADER_SIZE + 0xF4;
+const TITLE_SIZE: usize = 0x42;
+const DESCRIPTION_OFFSET: usize = FILE_HEADER_SIZE + 0x136;
+const DESCRIPTION_SIZE: usize = 0xCA;
+
+pub fn validate_title(title: &str) -> std::result::Result<(), String> {
+    if title.trim().is_empty() {
+        return Err("The title must not be empty.".to_string());
+    }
+    validate_text(title, TITLE_MAX_LENGTH, "title")
+}
+
+pub fn validate_description(description: &str) -> std::result::Result<(), String> {
+    validate_text(description, DESCRIPTION_MAX_LENGTH, "description")
+}
+
+fn validate_text(text: &str, max_length: usize, field: &str) -> std::result::Result<(), String> {
+    let length = text.chars().count();
+    if length > max_length {
+        return Err(format!(
+            "The {} is too long ({} of {} characters).",
+            field, length, max_length
+        ));
+    }
+    // Both values are edited with single line inputs.
+    if text.contains('\n') {
+        return Err(format!("The {} must not contain line breaks.", field));
+    }
+    if let Some(c) = text.chars().find(|c| c.is_control() || c.len_utf16() > 1) {
+        return Err(format!(
+            "The {} contains an unsupported character: {:?}",
+            field, c
+        ));
+    }
+    Ok(())
+}
+
+/// Creates a copy of the given course with replaced title and description.
+///
+/// Both values are written as UTF-16 into the decrypted course data. Its CRC32 checksum is then
+/// recomputed, since the game rejects courses with a mismatching checksum, before the data gets
+/// re-encrypted by `smmdb_lib`.
+pub fn set_course_metadata(course: &Course2, title: &str, description: &str) -> Result<Course2> {
+    validate_title(title).map_err(|err| anyhow!(err))?;
+    validate_description(description).map_err(|err| anyhow!(err))?;
+
+    let mut data = course.get_course_data().to_vec();
+    write_utf16(&mut data, TITLE_OFFSET, TITLE_SIZE, title)?;
+    write_utf16(&mut data, DESCRIPTION_OFFSET, DESCRIPTION_SIZE, description)?;
+    update_checksum(&mut data)?;
+
+    let thumb = course
+        .get_course_thumb()
+        .map(|thumb| thumb.clone().take_jpeg());
+    let mut edited_course = Course2::from_switch_files(data, thumb, false)
+        .map_err(|err| -> anyhow::Error { err.into() })?;
+    if let Some(smmdb_id) = course.get_smmdb_id() {
+        edited_course.set_smmdb_id(smmdb_id)?;
+    }
+    Ok(edited_course)
+}
+
+fn write_utf16(data: &mut [u8], offset: usize, size: usize, text: &str) -> Result<()> {
+    let field = data
+        .get_mut(offset..offset + size)
+        .ok_or_else(|| anyhow!("Course data is too short"))?;
+    let encoded: Vec<u8> = text
+        .encode_utf16()
+        .flat_map(|c| c.to_le_bytes().to_vec())
+        .collect();
+    // The last code unit is reserved for the null terminator.
+    if encoded.len() > size - 2 {
+        return Err(anyhow!("Text does not fit into course data"));
+    }
+    field.iter_mut().for_each(|b| *b = 0);
+    field[..encoded.len()].copy_from_slice(&encoded);
+    Ok(())
+}
+
+fn update_checksum(data: &mut [u8]) -> Result<()> {
+    let checksum = crc32fast::hash(
+        data.get(FILE_HEADER_SIZE..CHECKSUM_DATA_END)
+            .ok_or_else(|| anyhow!("Course data is too short"))?,
+    );
+    data[CHECKSUM_OFFSET..CHECKSUM_OFFSET + 4].copy_from_slice(&checksum.to_le_bytes());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Size of a course file including the encryption info.
+    const COURSE_FILE_SIZE: usize = 0x5C000;
+    const GAME_STYLE_OFFSET: usize = FILE_HEADER_SIZE + 0xF1;
+
+    fn read_utf16(data: &[u8]) -> String {
+        let units: Vec<u16> = data
+            .chunks(2)
+            .map(|c| u16::from_le_bytes([c[0], c[1]]))
+            .take_while(|c| *c != 0)
+            .collect();
+        String::from_utf16(&units).unwrap()
+    }
+
+    fn create_course_data() -> Vec<u8> {
+        let mut data = vec![0; COURSE_FILE_SIZE];
+        data[GAME_STYLE_OFFSET..GAME_STYLE_OFFSET + 2].copy_from_slice(b"M1");
+        write_utf16(&mut data, TITLE_OFFSET, TITLE_SIZE, "Old title").unwrap();
+        write_utf16(
+            &mut data,
+            DESCRIPTION_OFFSET,
+            DESCRIPTION_SIZE,
+            "Old description",
+        )
+        .unwrap();
+        update_checksum(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn accepts_valid_text() {
+        assert_eq!(validate_text("Mario's course", 32, "title"), Ok(()));
+        assert_eq!(validate_text(&"a".repeat(32), 32, "title"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_too_long_text() {
+        let err = validate_text(&"a".repeat(33), 32, "title").unwrap_err();
+        assert_eq!(err, "The title is too long (33 of 32 characters).");
+    }
+
+    #[test]
+    fn rejects_line_breaks() {
+        let err = validate_text("first\nsecond", 75, "description").unwrap_err();
+        assert_eq!(err, "The description must not contain line breaks.");
+    }
+
+    #[test]
+    fn rejects_unsupported_characters() {
+        assert!(validate_text("tab\t", 32, "title").is_err());
+        assert!(validate_text("emoji \u{1F600}", 32, "title").is_err());
+    }
+
+    #[test]
+    fn rejects_empty_title() {
+        assert!(validate_title("   ").is_err());
+        assert_eq!(validate_description(""), Ok(()));
+    }
+
+    #[test]
+    fn writes_null_terminated_utf16() {
+        let mut data = vec![0xFF; 0x10];
+        write_utf16(&mut data, 0x2, 0x8, "ab").unwrap();
+
+        assert_eq!(data[..0x2], [0xFF, 0xFF]);
+        assert_eq!(data[0x2..0xA], [b'a', 0, b'b', 0, 0, 0, 0, 0]);
+        assert_eq!(data[0xA..], [0xFF; 6]);
+    }
+
+    #[test]
+    fn zeroes_previous_text() {
+        let mut data = vec![0; 0x42];
+        write_utf16(&mut data, 0, 0x42, "A much longer title").unwrap();
+        write_utf16(&mut data, 0, 0x42, "Short").unwrap();
+
+        assert_eq!(read_utf16(&data), "Short");
+        assert!(data[0xA..].iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn rejects_text_without_space_for_null_terminator() {
+        let mut data = vec![0; 0x8];
+        assert!(write_utf16(&mut data, 0, 0x8, "abcd").is_err());
+        assert!(write_utf16(&mut data, 0, 0x8, "abc").is_ok());
+    }
+
+    #[test]
+    fn rejects_field_outside_of_data() {
+        let mut data = vec![0; 0x8];
+        assert!(write_utf16(&mut data, 0x4, 0x8, "a").is_err());
+    }
+
+    #[test]
+    fn updates_checksum_of_data_after_file_header() {
+        let mut data = vec![0; COURSE_FILE_SIZE];
+        data[0x0] = 0x1;
+        data[FILE_HEADER_SIZE] = 0x2;
+        data[CHECKSUM_DATA_END] = 0x3;
+
+        update_checksum(&mut data).unwrap();
+
+        let checksum = crc32fast::hash(&data[FILE_HEADER_SIZE..CHECKSUM_DATA_END]);
+        assert_eq!(
+            data[CHECKSUM_OFFSET..CHECKSUM_OFFSET + 4],
+            checksum.to_le_bytes()
+        );
+        assert_eq!(data[0x0], 0x1);
+    }
+
+    #[test]
+    fn rejects_checksum_of_too_short_data() {
+        let mut data = vec![0; CHECKSUM_DATA_END - 1];
+        assert!(update_checksum(&mut data).is_err());
+    }
+
+    #[test]
+    fn sets_course_metadata() {
+        let course = Course2::from_switch_files(create_course_data(), None, false).unwrap();
+
+        let edited_course = set_course_metadata(&course, "New title", "New description").unwrap();
+
+        let header = edited_course.get_course().get_header();
+        assert_eq!(header.get_title(), "New title");
+        assert_eq!(header.get_description(), "New description");
+        let data = edited_course.get_course_data();
+        assert_eq!(read_utf16(&data[TITLE_OFFSET..]), "New title");
+        let checksum = crc32fast::hash(&data[FILE_HEADER_SIZE..CHECKSUM_DATA_END]);
+        assert_eq!(
+            data[CHECKSUM_OFFSET..CHECKSUM_OFFSET + 4],
+            checksum.to_le_bytes()
+        );
+    }
+}
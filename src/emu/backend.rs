@@ -0,0 +1,232 @@
+use super::{
+    read_imkvdb, read_ryujinx_profiles, read_yuzu_profiles, EmuType, SaveDataType, UserProfile,
+};
+
+use anyhow::Result;
+use std::{
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
+
+pub const SMM2_TITLE_ID: &str = "01009B90006DC000";
+
+/// Describes how to find the SMM2 saves of an emulator.
+///
+/// Forks which reuse the NAND layout of their upstream emulator only need a new entry in
+/// [`EMU_BACKENDS`] with their own directory names.
+pub struct EmuBackend {
+    pub emu_type: EmuType,
+    /// Names of the emulator folder inside the data or config directory.
+    pub dir_names: &'static [&'static str],
+    /// Names of the data folder next to the executable in portable mode.
+    pub portable_dir_names: &'static [&'static str],
+    pub flatpak_ids: &'static [&'static str],
+    pub executables: &'static [&'static str],
+    /// Checks whether a folder is the data folder of this emulator.
+    pub is_emu_dir: fn(&Path) -> bool,
+    /// Lists all SMM2 save folders inside the data folder of this emulator.
+    pub find_saves: fn(&Path) -> Result<Vec<FoundSave>>,
+    /// Reads the user profiles of the emulated Switch.
+    pub read_profiles: fn(&Path) -> Result<Vec<UserProfile>>,
+}
+
+/// A SMM2 save folder together with the id of the user it belongs to.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FoundSave {
+    pub location: PathBuf,
+    pub user_id: Option<String>,
+}
+
+pub static EMU_BACKENDS: [EmuBackend; 6] = [
+    EmuBackend {
+        emu_type: EmuType::Yuzu,
+        dir_names: &["yuzu", "yuzu-emu"],
+        portable_dir_names: &["user"],
+        flatpak_ids: &["org.yuzu_emu.yuzu"],
+        executables: &["yuzu", "yuzu.exe", "yuzu-cmd"],
+        is_emu_dir: is_yuzu_dir,
+        find_saves: find_yuzu_saves,
+        read_profiles: read_yuzu_profiles,
+    },
+    EmuBackend {
+        emu_type: EmuType::Suyu,
+        dir_names: &["suyu"],
+        portable_dir_names: &["user"],
+        flatpak_ids: &[],
+        executables: &["suyu", "suyu.exe", "suyu-cmd"],
+        is_emu_dir: is_yuzu_dir,
+        find_saves: find_yuzu_saves,
+        read_profiles: read_yuzu_profiles,
+    },
+    EmuBackend {
+        emu_type: EmuType::Sudachi,
+        dir_names: &["sudachi"],
+        portable_dir_names: &["user"],
+        flatpak_ids: &[],
+        executables: &["sudachi", "sudachi.exe", "sudachi-cmd"],
+        is_emu_dir: is_yuzu_dir,
+        find_saves: find_yuzu_saves,
+        read_profiles: read_yuzu_profiles,
+    },
+    EmuBackend {
+        emu_type: EmuType::Citron,
+        dir_names: &["citron"],
+        portable_dir_names: &["user"],
+        flatpak_ids: &[],
+        executables: &["citron", "citron.exe", "citron-cmd"],
+        is_emu_dir: is_yuzu_dir,
+        find_saves: find_yuzu_saves,
+        read_profiles: read_yuzu_profiles,
+    },
+    EmuBackend {
+        emu_type: EmuType::Ryujinx,
+        dir_names: &["Ryujinx"],
+        portable_dir_names: &["portable"],
+        flatpak_ids: &["org.ryujinx.Ryujinx"],
+        executables: &["Ryujinx", "Ryujinx.exe", "Ryujinx.sh", "Ryujinx.Ava"],
+        is_emu_dir: is_ryujinx_dir,
+        find_saves: find_ryujinx_saves,
+        read_profiles: read_ryujinx_profiles,
+    },
+    // Ryubing keeps the folder and executable names of Ryujinx, so only its Flatpak can be told
+    // apart. All other installations are listed as Ryujinx.
+    EmuBackend {
+        emu_type: EmuType::Ryubing,
+        dir_names: &["Ryujinx"],
+        portable_dir_names: &[],
+        flatpak_ids: &["io.github.ryubing.Ryujinx"],
+        executables: &[],
+        is_emu_dir: is_ryujinx_dir,
+        find_saves: find_ryujinx_saves,
+        read_profiles: read_ryujinx_profiles,
+    },
+];
+
+impl EmuBackend {
+    /// Whether one of the executables of this emulator is located in the given folder.
+    pub fn has_executable_in(&self, dir: &Path) -> bool {
+        self.executables
+            .iter()
+            .any(|executable| dir.join(executable).is_file())
+    }
+
+    /// Whether the name of the folder contains the name of this emulator, e.g. `citron-windows`.
+    pub fn matches_dir_name(&self, dir: &Path) -> bool {
+        let file_name = match dir.file_name() {
+            Some(file_name) => file_name.to_string_lossy().to_lowercase(),
+            None => return false,
+        };
+        self.dir_names
+            .iter()
+            .any(|dir_name| file_name.contains(&dir_name.to_lowercase()))
+    }
+}
+
+/// Returns the backend of an emulator data folder.
+///
+/// Forks share the layout of their upstream emulator, so they are told apart by the name of the
+/// folder, or by the executable or the name of the installation folder for portable data
+/// folders. If this is not conclusive, the first matching backend is returned.
+pub fn find_backend(emu_dir: &Path) -> Option<&'static EmuBackend> {
+    let backends: Vec<&'static EmuBackend> = EMU_BACKENDS
+        .iter()
+        .filter(|backend| (backend.is_emu_dir)(emu_dir))
+        .collect();
+    let install_dir = emu_dir.parent();
+    backends
+        .iter()
+        .find(|backend| {
+            backend.matches_dir_name(emu_dir)
+                || install_dir.map_or(false, |install_dir| {
+                    backend.has_executable_in(install_dir) || backend.matches_dir_name(install_dir)
+                })
+        })
+        .or_else(|| backends.first())
+        .copied()
+}
+
+/// Returns the backends whose portable data folder might be located in the given installation
+/// folder.
+///
+/// Forks use the same portable folder name as their upstream emulator, so the backend is chosen
+/// by the executable inside of the installation folder or else by the name of the folder.
+pub fn find_portable_backends(install_dir: &Path) -> Vec<&'static EmuBackend> {
+    let backends: Vec<&'static EmuBackend> = EMU_BACKENDS
+        .iter()
+        .filter(|backend| backend.has_executable_in(install_dir))
+        .collect();
+    if !backends.is_empty() {
+        return backends;
+    }
+    EMU_BACKENDS
+        .iter()
+        .filter(|backend| backend.matches_dir_name(install_dir))
+        .collect()
+}
+
+/// Returns the lower case process names of all registered emulators.
+pub fn get_emulator_process_names() -> Vec<String> {
+    EMU_BACKENDS
+        .iter()
+        .flat_map(|backend| backend.executables.iter())
+        .map(|executable| executable.to_lowercase())
+        .collect()
+}
+
+pub fn is_yuzu_dir(path: &Path) -> bool {
+    path.join("nand").join("system").exists() && path.join("keys").exists()
+}
+
+pub fn is_ryujinx_dir(path: &Path) -> bool {
+    path.join("system").exists() && path.join("Config.json").exists()
+}
+
+/// Yuzu stores saves in `nand/user/save/0000000000000000/<user id>/<title id>`.
+pub fn find_yuzu_saves(emu_dir: &Path) -> Result<Vec<FoundSave>> {
+    let save_dir = emu_dir.join("nand/user/save/0000000000000000");
+    if !save_dir.exists() {
+        return Ok(vec![]);
+    }
+    let mut saves = vec![];
+    for entry in read_dir(save_dir)? {
+        let entry = entry?;
+        let mut path = entry.path();
+        if path.is_dir() {
+            path.push(SMM2_TITLE_ID);
+            if path.exists() {
+                saves.push(FoundSave {
+                    location: path,
+                    user_id: Some(entry.file_name().to_string_lossy().to_lowercase()),
+                });
+            }
+        }
+    }
+    Ok(saves)
+}
+
+/// Ryujinx maps title ids and user ids to save data ids in `imkvdb.arc`. Saves are then stored in
+/// `bis/user/save/<save data id>/0`.
+pub fn find_ryujinx_saves(emu_dir: &Path) -> Result<Vec<FoundSave>> {
+    let imkvdb_path = emu_dir.join("bis/system/save/8000000000000000/0/imkvdb.arc");
+    let save_dir = emu_dir.join("bis/user/save");
+    if !imkvdb_path.exists() {
+        return Ok(vec![]);
+    }
+
+    let mut saves = vec![];
+    for entry in read_imkvdb(&imkvdb_path)? {
+        if entry.get_title_id() != SMM2_TITLE_ID || entry.save_type != SaveDataType::Account {
+            continue;
+        }
+        let mut path = save_dir.clone();
+        path.push(entry.get_save_data_id());
+        path.push("0");
+        if path.exists() {
+            saves.push(FoundSave {
+                location: path,
+                user_id: Some(entry.get_user_id()?),
+            });
+        }
+    }
+    Ok(saves)
+}
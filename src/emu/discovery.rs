@@ -0,0 +1,127 @@
+use super::{find_backend, find_profile, EmuBackend, EmuSave, EmuType, UserProfile, SMM2_TITLE_ID};
+
+use std::{
+    fs::read_dir,
+    path::{Path, PathBuf},
+};
+
+/// Maximum directory depth which is searched below the picked directory.
+const MAX_DEPTH: usize = 10;
+
+/// Walks the given directory and collects all SMM2 save folders.
+///
+/// Emulator folders of any registered [`EmuBackend`] are resolved with the emulator specific
+/// lookup, e.g. via `imkvdb.arc` for Ryujinx. Other folders are accepted if they look like a
+/// SMM2 save folder.
+pub fn discover_saves(root: &Path) -> Vec<EmuSave> {
+    let mut saves = vec![];
+
+    // The picked directory might be located inside of an emulator folder.
+    for ancestor in root.ancestors().skip(1) {
+        if let Some(backend) = find_backend(ancestor) {
+            add_backend_saves(&mut saves, ancestor, backend, Some(root));
+            break;
+        }
+    }
+    discover_dir(&mut saves, root, 0);
+    saves
+}
+
+fn discover_dir(saves: &mut Vec<EmuSave>, dir: &Path, depth: usize) {
+    if let Some(backend) = find_backend(dir) {
+        add_backend_saves(saves, dir, backend, None);
+        return;
+    }
+    if is_save_dir(dir) {
+        add_save(saves, dir.to_path_buf(), guess_emu_type(dir), None);
+        return;
+    }
+    if depth >= MAX_DEPTH {
+        return;
+    }
+    let entries = match read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.filter_map(|entry| entry.ok()) {
+        let is_dir = entry
+            .file_type()
+            .map(|file_type| file_type.is_dir())
+            .unwrap_or(false);
+        if is_dir {
+            discover_dir(saves, &entry.path(), depth + 1);
+        }
+    }
+}
+
+fn add_backend_saves(
+    saves: &mut Vec<EmuSave>,
+    emu_dir: &Path,
+    backend: &EmuBackend,
+    filter: Option<&Path>,
+) {
+    let found_saves = match (backend.find_saves)(emu_dir) {
+        Ok(found_saves) => found_saves,
+        Err(_) => return,
+    };
+    let profiles = (backend.read_profiles)(emu_dir).unwrap_or_default();
+    found_saves
+        .into_iter()
+        .filter(|save| {
+            filter
+                .map(|filter| save.location.starts_with(filter))
+                .unwrap_or(true)
+        })
+        .for_each(|save| {
+            let profile = find_profile(&profiles, &save);
+            add_save(saves, save.location, Some(backend.emu_type), profile)
+        });
+}
+
+fn add_save(
+    saves: &mut Vec<EmuSave>,
+    location: PathBuf,
+    emu_type: Option<EmuType>,
+    profile: Option<UserProfile>,
+) {
+    if saves.iter().any(|save| save.get_location() == &location) {
+        return;
+    }
+    let display_name = location.to_string_lossy().into();
+    let mut save = EmuSave::new(display_name, location, emu_type);
+    save.set_profile(profile);
+    saves.push(save);
+}
+
+/// A SMM2 save folder contains a `save.dat` and either course files or is stored under the
+/// title id of SMM2.
+fn is_save_dir(dir: &Path) -> bool {
+    if !dir.join("save.dat").is_file() {
+        return false;
+    }
+    if dir.ends_with(SMM2_TITLE_ID) {
+        return true;
+    }
+    match read_dir(dir) {
+        Ok(entries) => entries.filter_map(|entry| entry.ok()).any(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .starts_with("course_data_")
+        }),
+        Err(_) => false,
+    }
+}
+
+fn guess_emu_type(dir: &Path) -> Option<EmuType> {
+    if dir.ends_with(SMM2_TITLE_ID) {
+        Some(EmuType::Yuzu)
+    } else if dir
+        .ancestors()
+        .any(|ancestor| ancestor.ends_with("bis/user/save"))
+    {
+        Some(EmuType::Ryujinx)
+    } else {
+        None
+    }
+}
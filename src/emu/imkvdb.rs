@@ -0,0 +1,296 @@
+use super::format_user_id;
+
+use anyhow::{anyhow, Result};
+use std::{
+    convert::TryInto,
+    fmt::{self, Display},
+    fs::read,
+    path::Path,
+};
+
+const IMKV_MAGIC: &[u8; 4] = b"IMKV";
+const IMEN_MAGIC: &[u8; 4] = b"IMEN";
+const IMKV_HEADER_SIZE: usize = 0xC;
+const IMEN_HEADER_SIZE: usize = 0xC;
+/// Size of the `SaveDataAttribute` key.
+const KEY_SIZE: usize = 0x40;
+/// Minimum size of the `SaveDataIndexerValue` value which contains the save data id.
+const VALUE_SIZE: usize = 0x8;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SaveDataType {
+    System,
+    Account,
+    Bcat,
+    Device,
+    Temporary,
+    Cache,
+    SystemBcat,
+    Unknown(u8),
+}
+
+impl From<u8> for SaveDataType {
+    fn from(save_type: u8) -> Self {
+        match save_type {
+            0 => SaveDataType::System,
+            1 => SaveDataType::Account,
+            2 => SaveDataType::Bcat,
+            3 => SaveDataType::Device,
+            4 => SaveDataType::Temporary,
+            5 => SaveDataType::Cache,
+            6 => SaveDataType::SystemBcat,
+            save_type => SaveDataType::Unknown(save_type),
+        }
+    }
+}
+
+impl Display for SaveDataType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SaveDataType::System => write!(f, "System"),
+            SaveDataType::Account => write!(f, "Account"),
+            SaveDataType::Bcat => write!(f, "Bcat"),
+            SaveDataType::Device => write!(f, "Device"),
+            SaveDataType::Temporary => write!(f, "Temporary"),
+            SaveDataType::Cache => write!(f, "Cache"),
+            SaveDataType::SystemBcat => write!(f, "SystemBcat"),
+            SaveDataType::Unknown(save_type) => write!(f, "Unknown ({})", save_type),
+        }
+    }
+}
+
+/// An entry of the save data index, which maps the attributes of a save to its save data id.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImkvdbEntry {
+    pub title_id: u64,
+    pub user_id: [u8; 16],
+    pub save_data_id: u64,
+    pub save_type: SaveDataType,
+}
+
+impl ImkvdbEntry {
+    /// Formats the title id the same way as it is written in save folder names.
+    pub fn get_title_id(&self) -> String {
+        format!("{:016X}", self.title_id)
+    }
+
+    /// Formats the save data id the same way as it is written in save folder names.
+    pub fn get_save_data_id(&self) -> String {
+        format!("{:016x}", self.save_data_id)
+    }
+
+    /// Formats the user id the same way as Ryujinx does in its `Profiles.json`.
+    pub fn get_user_id(&self) -> Result<String> {
+        format_user_id(&self.user_id[..8], &self.user_id[8..])
+    }
+}
+
+/// Reads the `imkvdb.arc` save data index of Ryujinx.
+pub fn read_imkvdb(path: &Path) -> Result<Vec<ImkvdbEntry>> {
+    parse_imkvdb(&read(path)?)
+}
+
+/// Parses an IMKV archive.
+///
+/// The archive starts with a header containing the `IMKV` magic and the entry count, followed by
+/// entries with an `IMEN` header, the key size and value size.
+pub fn parse_imkvdb(buffer: &[u8]) -> Result<Vec<ImkvdbEntry>> {
+    let header = get_bytes(buffer, 0, IMKV_HEADER_SIZE)?;
+    if &header[..4] != IMKV_MAGIC {
+        return Err(anyhow!("imkvdb.arc has an invalid magic"));
+    }
+    let entry_count = read_u32(header, 0x8)?;
+
+    let mut entries = vec![];
+    let mut offset = IMKV_HEADER_SIZE;
+    for index in 0..entry_count {
+        let entry_header = get_bytes(buffer, offset, IMEN_HEADER_SIZE)?;
+        if &entry_header[..4] != IMEN_MAGIC {
+            return Err(anyhow!("imkvdb.arc entry {} has an invalid magic", index));
+        }
+        let key_size = read_u32(entry_header, 0x4)? as usize;
+        let value_size = read_u32(entry_header, 0x8)? as usize;
+        if key_size < KEY_SIZE || value_size < VALUE_SIZE {
+            return Err(anyhow!(
+                "imkvdb.arc entry {} has an invalid size (key {:#x}, value {:#x})",
+                index,
+                key_size,
+                value_size
+            ));
+        }
+        offset += IMEN_HEADER_SIZE;
+        let key = get_bytes(buffer, offset, key_size)?;
+        offset += key_size;
+        let value = get_bytes(buffer, offset, value_size)?;
+        offset += value_size;
+
+        entries.push(ImkvdbEntry {
+            title_id: read_u64(key, 0x0)?,
+            user_id: get_bytes(key, 0x8, 0x10)?.try_into()?,
+            save_data_id: read_u64(value, 0x0)?,
+            save_type: key[0x20].into(),
+        });
+    }
+    Ok(entries)
+}
+
+fn get_bytes(buffer: &[u8], offset: usize, len: usize) -> Result<&[u8]> {
+    offset
+        .checked_add(len)
+        .and_then(|end| buffer.get(offset..end))
+        .ok_or_else(|| {
+            anyhow!(
+                "imkvdb.arc is truncated: expected {:#x} bytes at offset {:#x}, but file has {:#x} bytes",
+                len,
+                offset,
+                buffer.len()
+            )
+        })
+}
+
+fn read_u32(buffer: &[u8], offset: usize) -> Result<u32> {
+    Ok(u32::from_le_bytes(
+        get_bytes(buffer, offset, 4)?.try_into()?,
+    ))
+}
+
+fn read_u64(buffer: &[u8], offset: usize) -> Result<u64> {
+    Ok(u64::from_le_bytes(
+        get_bytes(buffer, offset, 8)?.try_into()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TITLE_ID: u64 = 0x0100_9B90_006D_C000;
+
+    fn build_header(entry_count: u32) -> Vec<u8> {
+        let mut buffer = IMKV_MAGIC.to_vec();
+        buffer.extend_from_slice(&[0; 4]);
+        buffer.extend_from_slice(&entry_count.to_le_bytes());
+        buffer
+    }
+
+    fn build_entry(title_id: u64, user_id: [u8; 16], save_type: u8, save_data_id: u64) -> Vec<u8> {
+        let mut key = vec![0; KEY_SIZE];
+        key[..0x8].copy_from_slice(&title_id.to_le_bytes());
+        key[0x8..0x18].copy_from_slice(&user_id);
+        key[0x20] = save_type;
+        let mut value = vec![0; 0x40];
+        value[..0x8].copy_from_slice(&save_data_id.to_le_bytes());
+
+        let mut buffer = IMEN_MAGIC.to_vec();
+        buffer.extend_from_slice(&(key.len() as u32).to_le_bytes());
+        buffer.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        buffer.extend(key);
+        buffer.extend(value);
+        buffer
+    }
+
+    fn build_archive(entries: &[Vec<u8>]) -> Vec<u8> {
+        let mut buffer = build_header(entries.len() as u32);
+        for entry in entries {
+            buffer.extend_from_slice(entry);
+        }
+        buffer
+    }
+
+    fn user_id() -> [u8; 16] {
+        let mut user_id = [0; 16];
+        for (index, byte) in user_id.iter_mut().enumerate() {
+            *byte = index as u8;
+        }
+        user_id
+    }
+
+    #[test]
+    fn parses_multiple_entries() {
+        let buffer = build_archive(&[
+            build_entry(TITLE_ID, user_id(), 1, 0x12),
+            build_entry(0x0100_0000_0000_1000, [0; 16], 3, 0xABCD),
+        ]);
+
+        let entries = parse_imkvdb(&buffer).unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].get_title_id(), "01009B90006DC000");
+        assert_eq!(
+            entries[0].get_user_id().unwrap(),
+            "07060504030201000f0e0d0c0b0a0908"
+        );
+        assert_eq!(entries[0].get_save_data_id(), "0000000000000012");
+        assert_eq!(entries[0].save_type, SaveDataType::Account);
+        assert_eq!(entries[1].get_title_id(), "0100000000001000");
+        assert_eq!(entries[1].get_save_data_id(), "000000000000abcd");
+        assert_eq!(entries[1].save_type, SaveDataType::Device);
+    }
+
+    #[test]
+    fn parses_empty_archive() {
+        assert_eq!(parse_imkvdb(&build_header(0)).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn rejects_truncated_header() {
+        let buffer = build_header(1);
+
+        assert!(parse_imkvdb(&buffer[..IMKV_HEADER_SIZE - 1]).is_err());
+    }
+
+    #[test]
+    fn rejects_truncated_entry() {
+        let buffer = build_archive(&[build_entry(TITLE_ID, user_id(), 1, 0x12)]);
+
+        assert!(parse_imkvdb(&buffer[..IMKV_HEADER_SIZE + IMEN_HEADER_SIZE - 1]).is_err());
+        assert!(parse_imkvdb(&buffer[..IMKV_HEADER_SIZE + IMEN_HEADER_SIZE + KEY_SIZE]).is_err());
+        assert!(parse_imkvdb(&buffer[..buffer.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn rejects_missing_entry() {
+        let mut buffer = build_archive(&[build_entry(TITLE_ID, user_id(), 1, 0x12)]);
+        buffer[0x8..0xC].copy_from_slice(&2u32.to_le_bytes());
+
+        assert!(parse_imkvdb(&buffer).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_imkv_magic() {
+        let mut buffer = build_archive(&[build_entry(TITLE_ID, user_id(), 1, 0x12)]);
+        buffer[0] = b'X';
+
+        let err = parse_imkvdb(&buffer).unwrap_err();
+        assert!(err.to_string().contains("invalid magic"));
+    }
+
+    #[test]
+    fn rejects_invalid_imen_magic() {
+        let mut buffer = build_archive(&[build_entry(TITLE_ID, user_id(), 1, 0x12)]);
+        buffer[IMKV_HEADER_SIZE] = b'X';
+
+        let err = parse_imkvdb(&buffer).unwrap_err();
+        assert!(err.to_string().contains("entry 0 has an invalid magic"));
+    }
+
+    #[test]
+    fn rejects_too_small_key() {
+        let mut buffer = build_archive(&[build_entry(TITLE_ID, user_id(), 1, 0x12)]);
+        let key_size = (KEY_SIZE as u32 - 1).to_le_bytes();
+        buffer[IMKV_HEADER_SIZE + 0x4..IMKV_HEADER_SIZE + 0x8].copy_from_slice(&key_size);
+
+        let err = parse_imkvdb(&buffer).unwrap_err();
+        assert!(err.to_string().contains("invalid size"));
+    }
+
+    #[test]
+    fn rejects_too_small_value() {
+        let mut buffer = build_archive(&[build_entry(TITLE_ID, user_id(), 1, 0x12)]);
+        let value_size = (VALUE_SIZE as u32 - 1).to_le_bytes();
+        buffer[IMKV_HEADER_SIZE + 0x8..IMKV_HEADER_SIZE + 0xC].copy_from_slice(&value_size);
+
+        let err = parse_imkvdb(&buffer).unwrap_err();
+        assert!(err.to_string().contains("invalid size"));
+    }
+}
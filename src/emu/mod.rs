@@ -2,179 +2,308 @@ use crate::components::SaveButton;
 
 use anyhow::Result;
 use std::{
-    collections::HashSet,
+    env,
     fmt::Write,
-    fs::{read_dir, File},
-    io::Read,
-    num::ParseIntError,
-    path::PathBuf,
+    fs::read_dir,
+    path::{Path, PathBuf},
 };
 
+mod backend;
+mod discovery;
+mod imkvdb;
+mod process;
+mod profile;
 mod save;
+mod scan;
 
+pub use backend::*;
+pub use discovery::*;
+pub use imkvdb::*;
+pub use process::*;
+pub use profile::*;
 pub use save::*;
+pub use scan::*;
 
-pub fn guess_emu_dir() -> Result<Vec<SaveButton>> {
-    let mut dirs = vec![];
-    let mut found_paths: HashSet<PathBuf> = HashSet::new();
-    let yuzu_guesses = ["yuzu", "yuzu-emu"];
-    let ryujinx_guesses = ["Ryujinx"];
-    if let Some(data_dir) = dirs::data_dir() {
-        guess_dir(
-            &mut dirs,
-            &mut found_paths,
-            data_dir.clone(),
-            &yuzu_guesses,
-            EmuType::Yuzu,
-            is_yuzu_dir,
-        )?;
-        guess_dir(
-            &mut dirs,
-            &mut found_paths,
-            data_dir,
-            &ryujinx_guesses,
-            EmuType::Ryujinx,
-            is_ryujinx_dir,
-        )?;
-    }
-    if let Some(config_dir) = dirs::config_dir() {
-        guess_dir(
-            &mut dirs,
-            &mut found_paths,
-            config_dir.clone(),
-            &yuzu_guesses,
-            EmuType::Yuzu,
-            is_yuzu_dir,
-        )?;
-        guess_dir(
-            &mut dirs,
-            &mut found_paths,
-            config_dir,
-            &ryujinx_guesses,
-            EmuType::Ryujinx,
-            is_ryujinx_dir,
-        )?;
-    }
-    if let Some(data_local_dir) = dirs::data_local_dir() {
-        guess_dir(
-            &mut dirs,
-            &mut found_paths,
-            data_local_dir.clone(),
-            &yuzu_guesses,
-            EmuType::Yuzu,
-            is_yuzu_dir,
-        )?;
-        guess_dir(
-            &mut dirs,
-            &mut found_paths,
-            data_local_dir,
-            &ryujinx_guesses,
-            EmuType::Ryujinx,
-            is_ryujinx_dir,
-        )?;
-    }
-    Ok(dirs)
+/// Folders relative to the home directory in which emulators are commonly extracted or AppImages
+/// are stored.
+const INSTALL_DIR_GUESSES: [&str; 6] = [
+    "Applications",
+    "Downloads",
+    "Emulators",
+    "Games",
+    "bin",
+    ".local/bin",
+];
+
+/// Runs a single step of the emulator scan.
+///
+/// Errors are never propagated. Instead every candidate folder which could not be used is
+/// reported as a [`ScanDiagnostic`].
+pub fn scan_emu_dirs(step: ScanStep, search_roots: &[PathBuf]) -> EmuScan {
+    let mut scan = EmuScan::default();
+    match step {
+        ScanStep::SearchRoots => {
+            for search_root in search_roots {
+                guess_search_root(&mut scan, search_root);
+            }
+        }
+        ScanStep::NativeDirs => {
+            for dir in [dirs::data_dir(), dirs::config_dir(), dirs::data_local_dir()]
+                .iter()
+                .flatten()
+            {
+                guess_native_dirs(&mut scan, dir);
+            }
+        }
+        ScanStep::FlatpakDirs => {
+            if let Some(home_dir) = dirs::home_dir() {
+                guess_flatpak_dirs(&mut scan, &home_dir);
+            }
+        }
+        ScanStep::InstallDirs => {
+            if let Some(home_dir) = dirs::home_dir() {
+                for install_dir in get_install_dirs(&home_dir) {
+                    guess_appimage_dirs(&mut scan, &install_dir);
+                    guess_portable_dirs(&mut scan, &install_dir);
+                }
+            }
+        }
+        ScanStep::ExecutableDirs => {
+            for executable_dir in get_executable_dirs() {
+                guess_portable_dirs(&mut scan, &executable_dir);
+            }
+        }
+    }
+    scan
 }
 
-fn guess_dir(
-    dirs: &mut Vec<SaveButton>,
-    found_paths: &mut HashSet<PathBuf>,
-    dir: PathBuf,
-    guesses: &[&str],
-    emu_type: EmuType,
-    is_emu_type: fn(PathBuf) -> bool,
-) -> Result<()> {
-    for guess in guesses.iter() {
-        let mut current_dir = dir.clone();
-        current_dir.push(guess);
-        if current_dir.as_path().exists() && is_emu_type(current_dir.clone()) {
-            match emu_type {
-                EmuType::Yuzu => {
-                    current_dir.push("nand/user/save/0000000000000000");
-                    if !current_dir.as_path().exists() {
-                        continue;
-                    }
-                    for entry in read_dir(current_dir.clone())? {
-                        let entry = entry?;
-                        let mut path = entry.path();
-                        if path.is_dir() {
-                            path.push("01009B90006DC000");
-                            if path.exists() && found_paths.get(&path).is_none() {
-                                found_paths.insert(path.clone());
-                                let display_name =
-                                    format!("[{:?}] {}", &emu_type, dir.to_string_lossy());
-                                dirs.push(SaveButton::new(display_name, path, emu_type.clone()));
-                            }
-                        }
-                    }
+/// Searches a user defined directory, which can either be an emulator folder itself or a folder
+/// containing emulator installations.
+pub fn guess_search_root(scan: &mut EmuScan, search_root: &Path) {
+    if !search_root.is_dir() {
+        scan.add_diagnostic(search_root, None, "search folder does not exist");
+        return;
+    }
+    // Forks share the folder layout of their upstream emulator, so the folder is only added once.
+    if let Some(backend) = find_backend(search_root) {
+        add_emu_dir(scan, search_root, backend);
+    }
+    guess_native_dirs(scan, search_root);
+    guess_appimage_dirs(scan, search_root);
+    guess_portable_dirs(scan, search_root);
+}
+
+/// Searches for default emulator installations, e.g. in `~/.local/share` or `%APPDATA%`.
+pub fn guess_native_dirs(scan: &mut EmuScan, dir: &Path) {
+    for (index, backend) in EMU_BACKENDS.iter().enumerate() {
+        // Folders of forks, which keep the folder name of their upstream emulator, are only
+        // checked once for the upstream emulator.
+        let dir_names: Vec<&str> = backend
+            .dir_names
+            .iter()
+            .filter(|dir_name| {
+                !EMU_BACKENDS[..index]
+                    .iter()
+                    .any(|upstream| upstream.dir_names.contains(dir_name))
+            })
+            .copied()
+            .collect();
+        guess_dir(scan, dir, &dir_names, backend);
+    }
+}
+
+/// Searches for Flatpak installations in `~/.var/app`.
+pub fn guess_flatpak_dirs(scan: &mut EmuScan, home_dir: &Path) {
+    let flatpak_dir = home_dir.join(".var/app");
+    for backend in EMU_BACKENDS.iter() {
+        for flatpak_id in backend.flatpak_ids.iter() {
+            for sub_dir in ["data", "config"].iter() {
+                guess_dir(
+                    scan,
+                    &flatpak_dir.join(flatpak_id).join(sub_dir),
+                    backend.dir_names,
+                    backend,
+                );
+            }
+        }
+    }
+}
+
+/// Searches for portable AppImage homes, which are created next to the AppImage via
+/// `--appimage-portable-home` or `--appimage-portable-config`.
+pub fn guess_appimage_dirs(scan: &mut EmuScan, install_dir: &Path) {
+    let entries = match read_dir(install_dir) {
+        Ok(entries) => entries,
+        Err(err) => {
+            scan.add_diagnostic(install_dir, None, format!("could not be read: {}", err));
+            return;
+        }
+    };
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(err) => {
+                scan.add_diagnostic(install_dir, None, format!("could not be read: {}", err));
+                continue;
+            }
+        };
+        let file_name = path
+            .file_name()
+            .map(|file_name| file_name.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if !path.is_dir() {
+            continue;
+        }
+        if file_name.ends_with(".appimage.home") {
+            guess_native_dirs(scan, &path.join(".local/share"));
+            guess_native_dirs(scan, &path.join(".config"));
+        } else if file_name.ends_with(".appimage.config") {
+            guess_native_dirs(scan, &path);
+        }
+    }
+}
+
+/// Searches for portable installations, which store their data in a folder next to the
+/// executable, e.g. `user` for Yuzu or `portable` for Ryujinx.
+///
+/// The given directory and all its direct sub directories are checked. Only emulators, whose
+/// executable or name is found in the checked directory, are considered.
+pub fn guess_portable_dirs(scan: &mut EmuScan, install_dir: &Path) {
+    let mut candidates = vec![install_dir.to_path_buf()];
+    match read_dir(install_dir) {
+        Ok(entries) => candidates.extend(
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir()),
+        ),
+        Err(err) => scan.add_diagnostic(install_dir, None, format!("could not be read: {}", err)),
+    }
+    for candidate in candidates {
+        for backend in find_portable_backends(&candidate) {
+            guess_dir(scan, &candidate, backend.portable_dir_names, backend);
+        }
+    }
+}
+
+fn get_install_dirs(home_dir: &Path) -> Vec<PathBuf> {
+    INSTALL_DIR_GUESSES
+        .iter()
+        .map(|guess| home_dir.join(guess))
+        .filter(|path| path.is_dir())
+        .collect()
+}
+
+/// Returns the directories of all emulator executables found in `PATH`.
+fn get_executable_dirs() -> Vec<PathBuf> {
+    let paths = match env::var_os("PATH") {
+        Some(paths) => paths,
+        None => return vec![],
+    };
+    let mut executable_dirs = vec![];
+    for path in env::split_paths(&paths) {
+        for backend in EMU_BACKENDS.iter() {
+            for executable in backend.executables.iter() {
+                let executable_path = path.join(executable);
+                if !executable_path.is_file() {
+                    continue;
                 }
-                EmuType::Ryujinx => {
-                    let display_name =
-                        format!("[{:?}] {}", &emu_type, current_dir.to_string_lossy());
-                    let mut imkvdb_path = current_dir.clone();
-                    imkvdb_path.push("bis/system/save/8000000000000000/0/imkvdb.arc");
-                    current_dir.push("bis/user/save");
-
-                    if imkvdb_path.exists() {
-                        let mut imkvdb = File::open(imkvdb_path)?;
-                        let mut buffer = vec![];
-                        imkvdb.read_to_end(&mut buffer)?;
-                        let mut game_id = decode_hex("01009B90006DC000")?;
-                        game_id.reverse();
-
-                        let imen_header_size = 0xC;
-                        for chunk in buffer[0xC..].chunks(0x80 + imen_header_size) {
-                            if game_id != chunk[imen_header_size..imen_header_size + 8].to_vec() {
-                                continue;
-                            }
-                            let mut save_data_chunks =
-                                chunk[imen_header_size + 0x40..imen_header_size + 0x48].to_vec();
-                            save_data_chunks.reverse();
-                            let save_data_id = encode_hex(&save_data_chunks)?;
-
-                            let mut path = current_dir.clone();
-                            path.push(save_data_id);
-                            path.push("0");
-
-                            if path.exists() && found_paths.get(&path).is_none() {
-                                found_paths.insert(path.clone());
-                                dirs.push(SaveButton::new(
-                                    display_name.clone(),
-                                    path,
-                                    emu_type.clone(),
-                                ));
-                            }
-                        }
+                if let Some(executable_dir) = executable_path
+                    .canonicalize()
+                    .ok()
+                    .and_then(|path| path.parent().map(Path::to_path_buf))
+                {
+                    if !executable_dirs.contains(&executable_dir) {
+                        executable_dirs.push(executable_dir);
                     }
                 }
             }
         }
     }
-    Ok(())
+    executable_dirs
 }
 
-pub fn is_yuzu_dir(path: PathBuf) -> bool {
-    let mut system_path = path.clone();
-    system_path.push("nand");
-    system_path.push("system");
-    let mut key_path = path;
-    key_path.push("keys");
-    system_path.as_path().exists() && key_path.as_path().exists()
+fn guess_dir(scan: &mut EmuScan, dir: &Path, guesses: &[&str], backend: &EmuBackend) {
+    for guess in guesses.iter() {
+        let emu_dir = dir.join(guess);
+        if !emu_dir.exists() {
+            continue;
+        }
+        if (backend.is_emu_dir)(&emu_dir) {
+            add_emu_dir(scan, &emu_dir, backend);
+        } else {
+            scan.add_diagnostic(
+                &emu_dir,
+                Some(backend.emu_type),
+                format!(
+                    "folder does not look like a {} data folder",
+                    backend.emu_type
+                ),
+            );
+        }
+    }
 }
 
-pub fn is_ryujinx_dir(path: PathBuf) -> bool {
-    let mut system_path = path.clone();
-    system_path.push("system");
-    let mut config_path = path;
-    config_path.push("Config.json");
-    system_path.as_path().exists() && config_path.as_path().exists()
+fn add_emu_dir(scan: &mut EmuScan, emu_dir: &Path, backend: &EmuBackend) {
+    let profiles = match (backend.read_profiles)(emu_dir) {
+        Ok(profiles) => profiles,
+        Err(err) => {
+            scan.add_diagnostic(
+                emu_dir,
+                Some(backend.emu_type),
+                format!("user profiles could not be read: {}", err),
+            );
+            vec![]
+        }
+    };
+    let saves = match (backend.find_saves)(emu_dir) {
+        Ok(saves) => saves,
+        Err(err) => {
+            scan.add_diagnostic(
+                emu_dir,
+                Some(backend.emu_type),
+                format!("saves could not be read: {}", err),
+            );
+            return;
+        }
+    };
+    if saves.is_empty() {
+        scan.add_diagnostic(
+            emu_dir,
+            Some(backend.emu_type),
+            "no Super Mario Maker 2 save found. Did you start the game at least once?",
+        );
+    }
+    for save in saves {
+        let profile = find_profile(&profiles, &save);
+        let display_name = match (&profile, &save.user_id) {
+            (Some(profile), _) => format!(
+                "[{}] {} {}",
+                &backend.emu_type,
+                profile,
+                emu_dir.to_string_lossy()
+            ),
+            (None, Some(user_id)) => format!(
+                "[{}] ({}) {}",
+                &backend.emu_type,
+                user_id,
+                emu_dir.to_string_lossy()
+            ),
+            (None, None) => format!("[{}] {}", &backend.emu_type, emu_dir.to_string_lossy()),
+        };
+        let location = save.location.clone();
+        scan.add_save(&location, || {
+            SaveButton::new(display_name, save.location, backend.emu_type, profile)
+        });
+    }
 }
 
-fn decode_hex(s: &str) -> Result<Vec<u8>, ParseIntError> {
-    (0..s.len())
-        .step_by(2)
-        .map(|i| u8::from_str_radix(&s[i..i + 2], 16))
-        .collect()
+fn find_profile(profiles: &[UserProfile], save: &FoundSave) -> Option<UserProfile> {
+    let user_id = save.user_id.as_ref()?;
+    profiles
+        .iter()
+        .find(|profile| profile.user_id.eq_ignore_ascii_case(user_id))
+        .cloned()
 }
 
 fn encode_hex(bytes: &[u8]) -> Result<String> {
@@ -184,3 +313,182 @@ fn encode_hex(bytes: &[u8]) -> Result<String> {
     }
     Ok(s)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::{create_dir_all, write};
+    use tempfile::TempDir;
+
+    const USER_ID: &str = "0123456789abcdef0123456789abcdef";
+
+    /// Creates a Yuzu data folder with a single SMM2 save and returns the save folder.
+    fn create_yuzu_dir(emu_dir: &Path) -> PathBuf {
+        create_dir_all(emu_dir.join("nand/system")).unwrap();
+        create_dir_all(emu_dir.join("keys")).unwrap();
+        let save_dir = emu_dir
+            .join("nand/user/save/0000000000000000")
+            .join(USER_ID)
+            .join(SMM2_TITLE_ID);
+        create_dir_all(&save_dir).unwrap();
+        write(save_dir.join("save.dat"), b"").unwrap();
+        save_dir
+    }
+
+    /// Creates a Ryujinx data folder without any saves.
+    fn create_ryujinx_dir(emu_dir: &Path) {
+        create_dir_all(emu_dir.join("system")).unwrap();
+        write(emu_dir.join("Config.json"), "{}").unwrap();
+    }
+
+    fn get_save_locations(scan: &EmuScan) -> Vec<PathBuf> {
+        scan.saves
+            .iter()
+            .map(|save| save.get_location().clone())
+            .collect()
+    }
+
+    fn has_diagnostic(scan: &EmuScan, path: &Path, emu_type: EmuType) -> bool {
+        scan.diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.path == path && diagnostic.emu_type == Some(emu_type))
+    }
+
+    #[test]
+    fn adds_search_root_of_fork_once() {
+        let emu_dir = TempDir::new().unwrap();
+        create_ryujinx_dir(emu_dir.path());
+
+        let mut scan = EmuScan::default();
+        guess_search_root(&mut scan, emu_dir.path());
+
+        let diagnostics: Vec<_> = scan
+            .diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.path == emu_dir.path())
+            .collect();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].emu_type, Some(EmuType::Ryujinx));
+    }
+
+    #[test]
+    fn finds_flatpak_data_dir() {
+        let home_dir = TempDir::new().unwrap();
+        let save_dir =
+            create_yuzu_dir(&home_dir.path().join(".var/app/org.yuzu_emu.yuzu/data/yuzu"));
+
+        let mut scan = EmuScan::default();
+        guess_flatpak_dirs(&mut scan, home_dir.path());
+
+        assert_eq!(get_save_locations(&scan), vec![save_dir]);
+    }
+
+    #[test]
+    fn finds_flatpak_config_dir() {
+        let home_dir = TempDir::new().unwrap();
+        let emu_dir = home_dir
+            .path()
+            .join(".var/app/org.ryujinx.Ryujinx/config/Ryujinx");
+        create_ryujinx_dir(&emu_dir);
+
+        let mut scan = EmuScan::default();
+        guess_flatpak_dirs(&mut scan, home_dir.path());
+
+        assert!(scan.saves.is_empty());
+        assert!(has_diagnostic(&scan, &emu_dir, EmuType::Ryujinx));
+    }
+
+    #[test]
+    fn ignores_missing_flatpak_dirs() {
+        let home_dir = TempDir::new().unwrap();
+
+        let mut scan = EmuScan::default();
+        guess_flatpak_dirs(&mut scan, home_dir.path());
+
+        assert!(scan.saves.is_empty());
+        assert!(scan.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn finds_appimage_portable_home() {
+        let install_dir = TempDir::new().unwrap();
+        write(install_dir.path().join("yuzu.AppImage"), b"").unwrap();
+        let save_dir = create_yuzu_dir(
+            &install_dir
+                .path()
+                .join("yuzu.AppImage.home/.local/share/yuzu"),
+        );
+
+        let mut scan = EmuScan::default();
+        guess_appimage_dirs(&mut scan, install_dir.path());
+
+        assert_eq!(get_save_locations(&scan), vec![save_dir]);
+    }
+
+    #[test]
+    fn finds_appimage_portable_config() {
+        let install_dir = TempDir::new().unwrap();
+        let save_dir = create_yuzu_dir(&install_dir.path().join("yuzu.AppImage.config/yuzu"));
+
+        let mut scan = EmuScan::default();
+        guess_appimage_dirs(&mut scan, install_dir.path());
+
+        assert_eq!(get_save_locations(&scan), vec![save_dir]);
+    }
+
+    #[test]
+    fn finds_portable_user_dir_next_to_executable() {
+        let install_dir = TempDir::new().unwrap();
+        let executable_dir = install_dir.path().join("yuzu-windows-msvc");
+        create_dir_all(&executable_dir).unwrap();
+        write(executable_dir.join("yuzu.exe"), b"").unwrap();
+        let save_dir = create_yuzu_dir(&executable_dir.join("user"));
+
+        let mut scan = EmuScan::default();
+        guess_portable_dirs(&mut scan, install_dir.path());
+
+        assert_eq!(get_save_locations(&scan), vec![save_dir]);
+    }
+
+    #[test]
+    fn finds_portable_dir_next_to_executable() {
+        let executable_dir = TempDir::new().unwrap();
+        write(executable_dir.path().join("Ryujinx"), b"").unwrap();
+        let emu_dir = executable_dir.path().join("portable");
+        create_ryujinx_dir(&emu_dir);
+
+        let mut scan = EmuScan::default();
+        guess_portable_dirs(&mut scan, executable_dir.path());
+
+        assert!(has_diagnostic(&scan, &emu_dir, EmuType::Ryujinx));
+    }
+
+    #[test]
+    fn labels_portable_fork_by_executable() {
+        let executable_dir = TempDir::new().unwrap();
+        write(executable_dir.path().join("citron.exe"), b"").unwrap();
+        let emu_dir = executable_dir.path().join("user");
+        create_dir_all(emu_dir.join("nand/system")).unwrap();
+        create_dir_all(emu_dir.join("keys")).unwrap();
+
+        let mut scan = EmuScan::default();
+        guess_portable_dirs(&mut scan, executable_dir.path());
+
+        assert!(has_diagnostic(&scan, &emu_dir, EmuType::Citron));
+        assert!(!has_diagnostic(&scan, &emu_dir, EmuType::Yuzu));
+    }
+
+    #[test]
+    fn reports_portable_dir_without_emulator_data() {
+        let executable_dir = TempDir::new().unwrap();
+        write(executable_dir.path().join("yuzu"), b"").unwrap();
+        let user_dir = executable_dir.path().join("user");
+        create_dir_all(&user_dir).unwrap();
+
+        let mut scan = EmuScan::default();
+        guess_portable_dirs(&mut scan, executable_dir.path());
+
+        assert!(scan.saves.is_empty());
+        assert!(has_diagnostic(&scan, &user_dir, EmuType::Yuzu));
+    }
+}
@@ -0,0 +1,233 @@
+use super::get_emulator_process_names;
+
+use std::{
+    ffi::OsStr,
+    fmt::{self, Display},
+    fs::{read, read_dir, read_link},
+    path::{Path, PathBuf},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunningEmulator {
+    pub pid: u32,
+    pub name: String,
+    /// Whether the process has files opened inside the directory of the save.
+    pub uses_save: bool,
+}
+
+impl Display for RunningEmulator {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} (pid {})", self.name, self.pid)
+    }
+}
+
+/// Lists emulator processes which are currently running on this system.
+///
+/// This is only supported on Linux. On other platforms an empty list is returned.
+pub fn find_running_emulators(save_dir: &Path) -> Vec<RunningEmulator> {
+    if cfg!(target_os = "linux") {
+        find_running_emulators_in(Path::new("/proc"), save_dir)
+    } else {
+        vec![]
+    }
+}
+
+/// Lists emulator processes by reading a procfs compatible directory structure.
+pub fn find_running_emulators_in(proc_dir: &Path, save_dir: &Path) -> Vec<RunningEmulator> {
+    let save_root = get_save_root(save_dir);
+    let process_names = get_emulator_process_names();
+    let entries = match read_dir(proc_dir) {
+        Ok(entries) => entries,
+        Err(_) => return vec![],
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_str()?.parse().ok()?;
+            let process_dir = entry.path();
+            let name = get_process_names(&process_dir)
+                .into_iter()
+                .find(|name| process_names.contains(&name.to_lowercase()))?;
+            let uses_save = get_open_paths(&process_dir)
+                .iter()
+                .any(|path| path.starts_with(&save_root));
+            Some(RunningEmulator {
+                pid,
+                name,
+                uses_save,
+            })
+        })
+        .collect()
+}
+
+/// Ryujinx stores a save in `bis/user/save/<save data id>/0` and writes it through the working
+/// copy in `1`, so the whole save data folder belongs to the save. Yuzu only uses the title folder.
+fn get_save_root(save_dir: &Path) -> PathBuf {
+    let is_ryujinx_save = save_dir.file_name() == Some(OsStr::new("0"))
+        && save_dir
+            .ancestors()
+            .any(|path| path.file_name() == Some(OsStr::new("bis")));
+    match save_dir.parent() {
+        Some(save_data_dir) if is_ryujinx_save => save_data_dir.to_path_buf(),
+        _ => save_dir.to_path_buf(),
+    }
+}
+
+/// Returns the executable name from `cmdline` and the process name from `comm`.
+fn get_process_names(process_dir: &Path) -> Vec<String> {
+    let mut names = vec![];
+    if let Ok(cmdline) = read(process_dir.join("cmdline")) {
+        let executable = cmdline.split(|b| *b == 0).next().unwrap_or_default();
+        let executable = String::from_utf8_lossy(executable);
+        if let Some(name) = Path::new(executable.as_ref()).file_name() {
+            names.push(name.to_string_lossy().into());
+        }
+    }
+    if let Ok(comm) = read(process_dir.join("comm")) {
+        names.push(String::from_utf8_lossy(&comm).trim().to_string());
+    }
+    names
+}
+
+fn get_open_paths(process_dir: &Path) -> Vec<PathBuf> {
+    let mut paths = vec![];
+    if let Ok(cwd) = read_link(process_dir.join("cwd")) {
+        paths.push(cwd);
+    }
+    if let Ok(fds) = read_dir(process_dir.join("fd")) {
+        paths.extend(
+            fds.filter_map(|fd| fd.ok())
+                .filter_map(|fd| read_link(fd.path()).ok()),
+        );
+    }
+    paths
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::{
+        fs::{create_dir_all, write},
+        os::unix::fs::symlink,
+    };
+    use tempfile::TempDir;
+
+    fn add_process(proc_dir: &Path, pid: u32, executable: &str, open_paths: &[PathBuf]) {
+        let process_dir = proc_dir.join(pid.to_string());
+        create_dir_all(process_dir.join("fd")).unwrap();
+        let comm = Path::new(executable).file_name().unwrap().to_string_lossy();
+        write(process_dir.join("comm"), format!("{}\n", comm)).unwrap();
+        write(
+            process_dir.join("cmdline"),
+            format!("{}\0--fullscreen\0", executable),
+        )
+        .unwrap();
+        for (fd, path) in open_paths.iter().enumerate() {
+            symlink(path, process_dir.join("fd").join(fd.to_string())).unwrap();
+        }
+    }
+
+    fn get_save_dir(emu_root: &Path) -> PathBuf {
+        emu_root.join("nand/user/save/0000000000000000/0123456789ABCDEF/01009B90006DC000")
+    }
+
+    #[test]
+    fn reports_emulator_using_save() {
+        let proc_dir = TempDir::new().unwrap();
+        let emu_root = Path::new("/home/user/.local/share/yuzu");
+        let save_dir = get_save_dir(emu_root);
+        add_process(
+            proc_dir.path(),
+            42,
+            "/usr/bin/yuzu",
+            &[PathBuf::from("/dev/null"), save_dir.join("save.dat")],
+        );
+
+        let running_emulators = find_running_emulators_in(proc_dir.path(), &save_dir);
+
+        assert_eq!(
+            running_emulators,
+            vec![RunningEmulator {
+                pid: 42,
+                name: "yuzu".to_string(),
+                uses_save: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_emulator_of_other_directory_as_not_using_save() {
+        let proc_dir = TempDir::new().unwrap();
+        let save_dir = get_save_dir(Path::new("/home/user/.local/share/yuzu"));
+        add_process(
+            proc_dir.path(),
+            7,
+            "/opt/Ryujinx/Ryujinx",
+            &[PathBuf::from(
+                "/home/user/.config/Ryujinx/bis/user/save/0000000000000001",
+            )],
+        );
+
+        let running_emulators = find_running_emulators_in(proc_dir.path(), &save_dir);
+
+        assert_eq!(running_emulators.len(), 1);
+        assert_eq!(running_emulators[0].pid, 7);
+        assert!(!running_emulators[0].uses_save);
+    }
+
+    #[test]
+    fn reports_emulator_with_only_log_open_as_not_using_save() {
+        let proc_dir = TempDir::new().unwrap();
+        let emu_root = Path::new("/home/user/.local/share/yuzu");
+        let save_dir = get_save_dir(emu_root);
+        add_process(
+            proc_dir.path(),
+            42,
+            "/usr/bin/yuzu",
+            &[emu_root.join("log/yuzu_log.txt")],
+        );
+
+        let running_emulators = find_running_emulators_in(proc_dir.path(), &save_dir);
+
+        assert_eq!(running_emulators.len(), 1);
+        assert!(!running_emulators[0].uses_save);
+    }
+
+    #[test]
+    fn reports_ryujinx_using_working_copy_of_save() {
+        let proc_dir = TempDir::new().unwrap();
+        let save_data_dir = Path::new("/home/user/.config/Ryujinx/bis/user/save/0000000000000001");
+        add_process(
+            proc_dir.path(),
+            7,
+            "/opt/Ryujinx/Ryujinx",
+            &[save_data_dir.join("1/save.dat")],
+        );
+
+        let running_emulators =
+            find_running_emulators_in(proc_dir.path(), &save_data_dir.join("0"));
+
+        assert_eq!(running_emulators.len(), 1);
+        assert!(running_emulators[0].uses_save);
+    }
+
+    #[test]
+    fn ignores_other_processes() {
+        let proc_dir = TempDir::new().unwrap();
+        let emu_root = Path::new("/home/user/.local/share/yuzu");
+        let save_dir = get_save_dir(emu_root);
+        add_process(
+            proc_dir.path(),
+            1,
+            "/usr/bin/file-manager",
+            &[save_dir.join("save.dat")],
+        );
+        add_process(proc_dir.path(), 2, "/usr/bin/yuzu-helper", &[]);
+        create_dir_all(proc_dir.path().join("self")).unwrap();
+        write(proc_dir.path().join("uptime"), "1.0 1.0").unwrap();
+
+        let running_emulators = find_running_emulators_in(proc_dir.path(), &save_dir);
+
+        assert!(running_emulators.is_empty());
+    }
+}
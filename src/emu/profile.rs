@@ -0,0 +1,125 @@
+use super::encode_hex;
+
+use anyhow::Result;
+use serde::Deserialize;
+use std::{
+    fmt::{self, Display},
+    fs::{read, read_dir},
+    path::Path,
+};
+
+const YUZU_PROFILES_DIR: &str = "nand/system/save/8000000000000010/su";
+const YUZU_USER_SIZE: usize = 0xC8;
+const YUZU_USERNAME_SIZE: usize = 0x20;
+
+/// A user profile of the emulated Switch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UserProfile {
+    /// User id as it is used in the save folder structure of the emulator.
+    pub user_id: String,
+    pub name: String,
+    /// JPEG encoded avatar image.
+    pub avatar: Option<Vec<u8>>,
+}
+
+impl Display for UserProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.user_id)
+    }
+}
+
+/// Formats a 128 bit user id stored as two little endian u64, printing the given halves in order.
+pub(super) fn format_user_id(first: &[u8], second: &[u8]) -> Result<String> {
+    let mut first = first.to_vec();
+    first.reverse();
+    let mut second = second.to_vec();
+    second.reverse();
+    Ok(format!("{}{}", encode_hex(&first)?, encode_hex(&second)?))
+}
+
+/// Yuzu stores up to 8 users in `profiles.dat`. Avatars are stored as JPEG files in the `avators`
+/// folder next to it.
+pub fn read_yuzu_profiles(emu_dir: &Path) -> Result<Vec<UserProfile>> {
+    let profiles_dir = emu_dir.join(YUZU_PROFILES_DIR);
+    let profiles_path = profiles_dir.join("avators/profiles.dat");
+    if !profiles_path.exists() {
+        return Ok(vec![]);
+    }
+    let buffer = read(profiles_path)?;
+    if buffer.len() < 0x10 {
+        return Ok(vec![]);
+    }
+
+    let mut profiles = vec![];
+    for user in buffer[0x10..].chunks_exact(YUZU_USER_SIZE) {
+        let uuid = &user[..0x10];
+        if uuid.iter().all(|b| *b == 0) {
+            continue;
+        }
+        let username = &user[0x28..0x28 + YUZU_USERNAME_SIZE];
+        let username_len = username
+            .iter()
+            .position(|b| *b == 0)
+            .unwrap_or(YUZU_USERNAME_SIZE);
+        let user_id = format_user_id(&uuid[8..], &uuid[..8])?;
+        profiles.push(UserProfile {
+            avatar: find_yuzu_avatar(&profiles_dir, uuid, &user_id),
+            user_id,
+            name: String::from_utf8_lossy(&username[..username_len]).into(),
+        });
+    }
+    Ok(profiles)
+}
+
+/// Depending on the Yuzu version the avatar file name is either formatted from the raw uuid bytes
+/// or from the user id, so both variants are compared ignoring dashes.
+fn find_yuzu_avatar(profiles_dir: &Path, uuid: &[u8], user_id: &str) -> Option<Vec<u8>> {
+    let uuid = encode_hex(uuid).ok()?;
+    let entries = read_dir(profiles_dir.join("avators")).ok()?;
+    entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| {
+            let is_jpg = path
+                .extension()
+                .map(|extension| extension.eq_ignore_ascii_case("jpg"))
+                .unwrap_or(false);
+            let name = path
+                .file_stem()
+                .map(|name| name.to_string_lossy().replace('-', "").to_lowercase())
+                .unwrap_or_default();
+            is_jpg && (name == uuid || name == user_id.to_lowercase())
+        })
+        .and_then(|path| read(path).ok())
+}
+
+#[derive(Deserialize)]
+struct RyujinxProfiles {
+    profiles: Vec<RyujinxProfile>,
+}
+
+#[derive(Deserialize)]
+struct RyujinxProfile {
+    user_id: String,
+    name: String,
+    #[serde(default)]
+    image: Option<String>,
+}
+
+/// Ryujinx stores its users in `system/Profiles.json` with base64 encoded avatars.
+pub fn read_ryujinx_profiles(emu_dir: &Path) -> Result<Vec<UserProfile>> {
+    let profiles_path = emu_dir.join("system/Profiles.json");
+    if !profiles_path.exists() {
+        return Ok(vec![]);
+    }
+    let profiles: RyujinxProfiles = serde_json::from_slice(&read(profiles_path)?)?;
+    Ok(profiles
+        .profiles
+        .into_iter()
+        .map(|profile| UserProfile {
+            user_id: profile.user_id.to_lowercase(),
+            name: profile.name,
+            avatar: profile.image.and_then(|image| base64::decode(image).ok()),
+        })
+        .collect())
+}
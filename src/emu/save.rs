@@ -1,3 +1,6 @@
+use super::UserProfile;
+
+use serde::{Deserialize, Serialize};
 use std::{
     fmt::{self, Display},
     path::PathBuf,
@@ -7,21 +10,38 @@ use std::{
 pub struct EmuSave {
     display_name: String,
     location: PathBuf,
-    emu_type: EmuType,
+    emu_type: Option<EmuType>,
+    profile: Option<UserProfile>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub enum EmuType {
     Yuzu,
+    Suyu,
+    Sudachi,
+    Citron,
     Ryujinx,
+    Ryubing,
+}
+
+impl EmuType {
+    pub const ALL: [EmuType; 6] = [
+        EmuType::Yuzu,
+        EmuType::Suyu,
+        EmuType::Sudachi,
+        EmuType::Citron,
+        EmuType::Ryujinx,
+        EmuType::Ryubing,
+    ];
 }
 
 impl EmuSave {
-    pub fn new(display_name: String, location: PathBuf, emu_type: EmuType) -> EmuSave {
+    pub fn new(display_name: String, location: PathBuf, emu_type: Option<EmuType>) -> EmuSave {
         EmuSave {
             display_name,
             location,
             emu_type,
+            profile: None,
         }
     }
 
@@ -32,10 +52,37 @@ impl EmuSave {
     pub fn get_location(&self) -> &PathBuf {
         &self.location
     }
+
+    pub fn get_profile(&self) -> Option<&UserProfile> {
+        self.profile.as_ref()
+    }
+
+    pub fn set_profile(&mut self, profile: Option<UserProfile>) {
+        self.profile = profile;
+    }
 }
 
 impl Display for EmuSave {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "[{:?}] {:?}", self.emu_type, self.location)
+        if let Some(emu_type) = &self.emu_type {
+            write!(f, "[{}] ", emu_type)?;
+        }
+        if let Some(profile) = &self.profile {
+            write!(f, "{} ", profile)?;
+        }
+        write!(f, "{:?}", self.location)
+    }
+}
+
+impl Display for EmuType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EmuType::Yuzu => write!(f, "Yuzu"),
+            EmuType::Suyu => write!(f, "suyu"),
+            EmuType::Sudachi => write!(f, "Sudachi"),
+            EmuType::Citron => write!(f, "Citron"),
+            EmuType::Ryujinx => write!(f, "Ryujinx"),
+            EmuType::Ryubing => write!(f, "Ryubing"),
+        }
     }
 }
@@ -0,0 +1,95 @@
+use super::EmuType;
+use crate::components::SaveButton;
+
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+    path::{Path, PathBuf},
+};
+
+/// The emulator scan is split into steps, so that results can be shown while the scan is still
+/// running.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ScanStep {
+    SearchRoots,
+    NativeDirs,
+    FlatpakDirs,
+    InstallDirs,
+    ExecutableDirs,
+}
+
+impl ScanStep {
+    pub const ALL: [ScanStep; 5] = [
+        ScanStep::SearchRoots,
+        ScanStep::NativeDirs,
+        ScanStep::FlatpakDirs,
+        ScanStep::InstallDirs,
+        ScanStep::ExecutableDirs,
+    ];
+
+    pub fn next(&self) -> Option<ScanStep> {
+        let index = ScanStep::ALL.iter().position(|step| step == self)?;
+        ScanStep::ALL.get(index + 1).copied()
+    }
+}
+
+impl Display for ScanStep {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScanStep::SearchRoots => write!(f, "additional search folders"),
+            ScanStep::NativeDirs => write!(f, "default installations"),
+            ScanStep::FlatpakDirs => write!(f, "Flatpak installations"),
+            ScanStep::InstallDirs => write!(f, "AppImage and portable installations"),
+            ScanStep::ExecutableDirs => write!(f, "installations in PATH"),
+        }
+    }
+}
+
+/// Explains why a candidate folder did not yield a save.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScanDiagnostic {
+    pub path: PathBuf,
+    pub emu_type: Option<EmuType>,
+    pub reason: String,
+}
+
+impl Display for ScanDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(emu_type) = &self.emu_type {
+            write!(f, "[{}] ", emu_type)?;
+        }
+        write!(f, "{}: {}", self.path.to_string_lossy(), self.reason)
+    }
+}
+
+/// Collects the saves and diagnostics of an emulator scan.
+#[derive(Clone, Debug, Default)]
+pub struct EmuScan {
+    pub saves: Vec<SaveButton>,
+    pub diagnostics: Vec<ScanDiagnostic>,
+    found_paths: HashSet<PathBuf>,
+}
+
+impl EmuScan {
+    pub fn add_save(&mut self, location: &Path, create: impl FnOnce() -> SaveButton) {
+        if self.found_paths.insert(location.to_path_buf()) {
+            self.saves.push(create());
+        }
+    }
+
+    pub fn add_diagnostic(
+        &mut self,
+        path: &Path,
+        emu_type: Option<EmuType>,
+        reason: impl Into<String>,
+    ) {
+        let diagnostic = ScanDiagnostic {
+            path: path.to_path_buf(),
+            emu_type,
+            reason: reason.into(),
+        };
+        if !self.diagnostics.contains(&diagnostic) {
+            self.diagnostics.push(diagnostic);
+        }
+    }
+}
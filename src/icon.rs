@@ -10,6 +10,9 @@ lazy_static! {
     pub static ref SORT: Svg = Svg::new(svg::Handle::from_memory(
         include_bytes!("../assets/icons/sort.svg").to_vec(),
     ));
+    pub static ref EDIT: Svg = Svg::new(svg::Handle::from_memory(
+        include_bytes!("../assets/icons/edit.svg").to_vec(),
+    ));
     pub static ref DELETE: Svg = Svg::new(svg::Handle::from_memory(
         include_bytes!("../assets/icons/delete.svg").to_vec(),
     ));
@@ -2,12 +2,17 @@
 extern crate lazy_static;
 
 mod app;
+mod backup;
 mod components;
+mod course_edit;
 mod download;
 mod emu;
 mod font;
 mod icon;
 mod pages;
+mod save_diff;
+mod save_filter;
+mod save_watcher;
 mod settings;
 mod smmdb;
 mod styles;
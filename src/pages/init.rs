@@ -1,30 +1,171 @@
-use crate::{components::SaveButton, font::*, styles::*, AppErrorState, AppState, Message};
+use crate::{
+    components::SaveButton,
+    emu::{EmuScan, ScanDiagnostic},
+    font::*,
+    icon,
+    styles::*,
+    AppErrorState, AppState, EmuType, Message, Settings,
+};
 
-use iced::{button, Button, Column, Element, Length, Space, Text};
+use iced::{
+    button, pick_list, scrollable, text_input, Align, Button, Checkbox, Column, Element, Length,
+    PickList, Row, Scrollable, Space, Text, TextInput,
+};
+use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
 pub struct InitPage {
+    state: scrollable::State,
     open_custom_save: button::State,
+    open_backup_folder: button::State,
+    open_backup_zip: button::State,
     save_buttons: Vec<SaveButton>,
+    scan_generation: usize,
+    is_scanning: bool,
+    scan_diagnostics: Vec<ScanDiagnostic>,
+    show_diagnostics: bool,
+    diagnostics_state: button::State,
+    registered_save_buttons: Vec<SaveButton>,
+    discovered_save_buttons: Vec<SaveButton>,
+    search_roots: Vec<(PathBuf, button::State)>,
+    add_search_root: button::State,
+    register_save: button::State,
+    register_name_state: text_input::State,
+    register_emu_type_state: pick_list::State<EmuType>,
+    register_name: String,
+    register_emu_type: EmuType,
+    read_only: bool,
 }
 
 impl InitPage {
-    pub fn new(save_buttons: Vec<SaveButton>) -> InitPage {
-        InitPage {
+    pub fn new(save_buttons: Vec<SaveButton>, settings: &Settings) -> InitPage {
+        let mut init_page = InitPage {
+            state: scrollable::State::new(),
             open_custom_save: button::State::new(),
+            open_backup_folder: button::State::new(),
+            open_backup_zip: button::State::new(),
             save_buttons,
+            scan_generation: 0,
+            is_scanning: false,
+            scan_diagnostics: vec![],
+            show_diagnostics: false,
+            diagnostics_state: button::State::new(),
+            registered_save_buttons: vec![],
+            discovered_save_buttons: vec![],
+            search_roots: vec![],
+            add_search_root: button::State::new(),
+            register_save: button::State::new(),
+            register_name_state: text_input::State::new(),
+            register_emu_type_state: pick_list::State::default(),
+            register_name: String::new(),
+            register_emu_type: EmuType::Yuzu,
+            read_only: false,
+        };
+        init_page.set_settings(settings);
+        init_page
+    }
+
+    /// Clears the previous scan results. Results of older scans are ignored from now on.
+    pub fn start_scan(&mut self) -> usize {
+        self.scan_generation += 1;
+        self.is_scanning = true;
+        self.save_buttons.clear();
+        self.scan_diagnostics.clear();
+        self.scan_generation
+    }
+
+    /// Adds the results of a scan step. Returns false, if the scan has been superseded.
+    pub fn add_scan(&mut self, generation: usize, scan: EmuScan) -> bool {
+        if generation != self.scan_generation {
+            return false;
+        }
+        for save_button in scan.saves {
+            if !self
+                .save_buttons
+                .iter()
+                .any(|existing| existing.get_location() == save_button.get_location())
+            {
+                self.save_buttons.push(save_button);
+            }
+        }
+        for diagnostic in scan.diagnostics {
+            if !self.scan_diagnostics.contains(&diagnostic) {
+                self.scan_diagnostics.push(diagnostic);
+            }
         }
+        true
+    }
+
+    pub fn finish_scan(&mut self) {
+        self.is_scanning = false;
+    }
+
+    pub fn toggle_diagnostics(&mut self) {
+        self.show_diagnostics = !self.show_diagnostics;
+    }
+
+    pub fn set_discovered_saves(&mut self, discovered_save_buttons: Vec<SaveButton>) {
+        self.discovered_save_buttons = discovered_save_buttons;
+    }
+
+    pub fn set_settings(&mut self, settings: &Settings) {
+        self.registered_save_buttons = settings
+            .registered_saves
+            .iter()
+            .map(SaveButton::from_registered)
+            .collect();
+        self.search_roots = settings
+            .search_roots
+            .iter()
+            .map(|search_root| (search_root.clone(), button::State::new()))
+            .collect();
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    pub fn set_register_name(&mut self, register_name: String) {
+        self.register_name = register_name;
+    }
+
+    pub fn set_register_emu_type(&mut self, register_emu_type: EmuType) {
+        self.register_emu_type = register_emu_type;
+    }
+
+    pub fn take_register_values(&mut self) -> (String, EmuType) {
+        (
+            std::mem::take(&mut self.register_name),
+            self.register_emu_type,
+        )
     }
 }
 
 impl InitPage {
     pub fn view(&mut self, state: &AppState, error_state: &AppErrorState) -> Element<Message> {
-        let mut content = self.save_buttons.iter_mut().fold(
-            Column::new()
-                .padding(CONTAINER_PADDING)
-                .spacing(LIST_SPACING),
-            |acc, save_button| acc.push(save_button.view(state)),
-        );
+        let mut content = self
+            .save_buttons
+            .iter_mut()
+            .chain(self.registered_save_buttons.iter_mut())
+            .fold(
+                Column::new()
+                    .padding(CONTAINER_PADDING)
+                    .spacing(LIST_SPACING),
+                |acc, save_button| acc.push(save_button.view(state)),
+            );
+
+        if self.is_scanning {
+            content =
+                content.push(Text::new("Searching for emulator saves...").color(TEXT_HELP_COLOR));
+        } else if self.save_buttons.is_empty() {
+            content = content.push(
+                Text::new("No emulator save has been found automatically.").color(TEXT_HELP_COLOR),
+            );
+        }
 
         let mut custom_save_button = Button::new(
             &mut self.open_custom_save,
@@ -36,7 +177,42 @@ impl InitPage {
             AppState::Loading => custom_save_button,
             _ => custom_save_button.on_press(Message::OpenCustomSave),
         };
-        content = content.push(custom_save_button);
+        let mut backup_folder_button = Button::new(
+            &mut self.open_backup_folder,
+            Text::new("Open JKSV / Checkpoint backup folder"),
+        )
+        .padding(BUTTON_PADDING)
+        .style(DefaultButtonStyle);
+        let mut backup_zip_button =
+            Button::new(&mut self.open_backup_zip, Text::new("Open JKSV backup zip"))
+                .padding(BUTTON_PADDING)
+                .style(DefaultButtonStyle);
+        if state != &AppState::Loading {
+            backup_folder_button = backup_folder_button.on_press(Message::OpenBackup(false));
+            backup_zip_button = backup_zip_button.on_press(Message::OpenBackup(true));
+        }
+        let backup_buttons = Row::new()
+            .spacing(LIST_SPACING)
+            .push(backup_folder_button)
+            .push(backup_zip_button);
+
+        content = content
+            .push(custom_save_button)
+            .push(backup_buttons)
+            .push(Checkbox::new(
+                self.read_only,
+                "Open read-only (no changes will be written to the save)",
+                Message::SetReadOnly,
+            ));
+
+        if !self.discovered_save_buttons.is_empty() {
+            content = self.discovered_save_buttons.iter_mut().fold(
+                content
+                    .push(Space::with_height(Length::Units(16)))
+                    .push(Text::new("Found saves in the selected folder:").font(HELVETICA_BOLD)),
+                |acc, save_button| acc.push(save_button.view(state)),
+            );
+        }
 
         content = if let AppErrorState::Some(err) = error_state {
             content.push(Space::with_height(Length::Units(16))).push(
@@ -49,9 +225,116 @@ impl InitPage {
             content
         };
 
-        Column::new()
+        let mut register_save_button =
+            Button::new(&mut self.register_save, Text::new("Register save folder"))
+                .padding(BUTTON_PADDING)
+                .style(DefaultButtonStyle);
+        if state != &AppState::Loading {
+            register_save_button = register_save_button.on_press(Message::RegisterSave);
+        }
+        let register_content = Column::new()
+            .padding(CONTAINER_PADDING)
+            .spacing(LIST_SPACING)
+            .push(Text::new("Register a save folder:").font(HELVETICA_BOLD))
+            .push(
+                Row::new()
+                    .align_items(Align::Center)
+                    .spacing(LIST_SPACING)
+                    .push(
+                        TextInput::new(
+                            &mut self.register_name_state,
+                            "Display name",
+                            &self.register_name,
+                            Message::RegisterSaveNameChanged,
+                        )
+                        .style(DefaultTextInputStyle)
+                        .padding(4)
+                        .width(Length::Units(320)),
+                    )
+                    .push(
+                        PickList::new(
+                            &mut self.register_emu_type_state,
+                            &EmuType::ALL[..],
+                            Some(self.register_emu_type),
+                            Message::RegisterSaveEmuTypeChanged,
+                        )
+                        .style(DefaultPickListStyle)
+                        .padding(4),
+                    )
+                    .push(register_save_button),
+            );
+
+        let search_roots_content = self.search_roots.iter_mut().fold(
+            Column::new()
+                .padding(CONTAINER_PADDING)
+                .spacing(LIST_SPACING)
+                .push(Text::new("Additional emulator search folders:").font(HELVETICA_BOLD)),
+            |acc, (search_root, remove_state)| {
+                acc.push(
+                    Row::new()
+                        .align_items(Align::Center)
+                        .push(Text::new(search_root.to_string_lossy()))
+                        .push(Space::with_width(Length::Units(10)))
+                        .push(
+                            Button::new(
+                                remove_state,
+                                icon::DELETE
+                                    .clone()
+                                    .width(Length::Units(24))
+                                    .height(Length::Units(24)),
+                            )
+                            .style(DeleteButtonStyle)
+                            .on_press(Message::RemoveSearchRoot(search_root.clone())),
+                        ),
+                )
+            },
+        );
+        let mut add_search_root_button =
+            Button::new(&mut self.add_search_root, Text::new("Add search folder"))
+                .padding(BUTTON_PADDING)
+                .style(DefaultButtonStyle);
+        if state != &AppState::Loading {
+            add_search_root_button = add_search_root_button.on_press(Message::AddSearchRoot);
+        }
+        let search_roots_content = search_roots_content.push(add_search_root_button);
+
+        let diagnostics_label = if self.show_diagnostics {
+            "Hide scan details"
+        } else {
+            "Why wasn't my save found?"
+        };
+        let mut diagnostics_content = Column::new()
+            .padding(CONTAINER_PADDING)
+            .spacing(LIST_SPACING)
+            .push(
+                Button::new(&mut self.diagnostics_state, Text::new(diagnostics_label))
+                    .padding(BUTTON_PADDING)
+                    .style(DefaultButtonStyle)
+                    .on_press(Message::ToggleScanDiagnostics),
+            );
+        if self.show_diagnostics {
+            diagnostics_content = if self.scan_diagnostics.is_empty() {
+                diagnostics_content.push(
+                    Text::new(
+                        "No emulator folder has been found. Add the folder containing your emulator as a search folder or register your save folder manually.",
+                    )
+                    .color(TEXT_HELP_COLOR),
+                )
+            } else {
+                self.scan_diagnostics
+                    .iter()
+                    .fold(diagnostics_content, |acc, diagnostic| {
+                        acc.push(Text::new(diagnostic.to_string()).size(16))
+                    })
+            };
+        }
+
+        Scrollable::new(&mut self.state)
             .push(Text::new("Please select your save folder").size(36))
             .push(content)
+            .push(register_content)
+            .push(search_roots_content)
+            .push(diagnostics_content)
             .padding(CONTAINER_PADDING)
             .spacing(PAGE_SPACING)
             .into()
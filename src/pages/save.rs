@@ -1,52 +1,539 @@
 use crate::{
-    smmdb::Course2Response,
-    widgets::{SaveWidget, SmmdbTab, SmmdbWidget},
-    AppState, Message, Smmdb,
+    backup::SaveBackup,
+    components::CoursePanel,
+    course_edit,
+    emu::{find_running_emulators, RunningEmulator},
+    font::*,
+    save_diff::{DiffFormat, SaveDiff},
+    save_filter::SaveFilter,
+    save_watcher::{self, SaveFingerprint},
+    smmdb::{Course2Response, CourseUpdate},
+    styles::*,
+    widgets::{
+        DiffWidget, SaveWidget, SlotOption, SmmdbTab, SmmdbWidget, TransferDirection,
+        TransferWidget, UpdatesWidget,
+    },
+    AppErrorState, AppState, Message, Smmdb,
 };
 
-use anyhow::Result;
-use iced::{Element, Row};
-use std::collections::HashMap;
+use anyhow::{anyhow, Result};
+use iced::{button, Align, Button, Column, Element, Length, Row, Space, Text};
+use smmdb_lib::CourseEntry;
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Path, PathBuf},
+};
 
 #[derive(Clone, Debug)]
 pub struct SavePage {
     save: smmdb_lib::Save,
     display_name: String,
+    location: PathBuf,
+    read_only: bool,
+    backup: Option<SaveBackup>,
+    backup_message: Option<String>,
+    fingerprint: Option<SaveFingerprint>,
+    is_modified_externally: bool,
+    /// Slots which have been written by us since the save has been loaded.
+    changed_slots: BTreeSet<u8>,
+    running_emulators: Vec<RunningEmulator>,
     save_widget: SaveWidget,
+    /// Slots of the courses in this save by their SMMDB id.
+    installed_courses: HashMap<String, Vec<usize>>,
     smmdb_widget: SmmdbWidget,
+    /// Second save, which is shown instead of the SMMDB widget.
+    other: Option<Box<SavePage>>,
+    transfer_widget: Option<TransferWidget>,
+    diff_widget: Option<DiffWidget>,
+    updates_widget: Option<UpdatesWidget>,
+    reload_state: button::State,
+    merge_state: button::State,
+    overwrite_state: button::State,
+    export_state: button::State,
+    open_other_state: button::State,
+    open_other_zip_state: button::State,
+    check_updates_state: button::State,
 }
 
 impl SavePage {
     pub fn new(
         save: smmdb_lib::Save,
         display_name: String,
+        location: PathBuf,
+        read_only: bool,
+        backup: Option<SaveBackup>,
+        fingerprint: Option<SaveFingerprint>,
         course_responses: &HashMap<String, Course2Response>,
     ) -> SavePage {
         SavePage {
             save_widget: SaveWidget::new(&save, course_responses),
+            installed_courses: get_installed_courses(&save),
             save,
             display_name,
+            fingerprint,
+            running_emulators: vec![],
+            location,
+            read_only,
+            backup,
+            backup_message: None,
+            is_modified_externally: false,
+            changed_slots: BTreeSet::new(),
             smmdb_widget: SmmdbWidget::new(),
+            other: None,
+            transfer_widget: None,
+            diff_widget: None,
+            updates_widget: None,
+            reload_state: button::State::new(),
+            merge_state: button::State::new(),
+            overwrite_state: button::State::new(),
+            export_state: button::State::new(),
+            open_other_state: button::State::new(),
+            open_other_zip_state: button::State::new(),
+            check_updates_state: button::State::new(),
+        }
+    }
+
+    pub fn get_display_name(&self) -> &String {
+        &self.display_name
+    }
+
+    pub fn get_location(&self) -> &PathBuf {
+        &self.location
+    }
+
+    /// Identifies the save across sessions, i.e. the original backup instead of its working copy.
+    pub fn get_source_location(&self) -> &PathBuf {
+        match &self.backup {
+            Some(backup) => backup.get_source(),
+            None => &self.location,
+        }
+    }
+
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    pub fn set_other(&mut self, mut other: SavePage) {
+        other.save_widget.disable_filter();
+        self.transfer_widget = Some(TransferWidget::new(&self.save, &other.save));
+        self.other = Some(Box::new(other));
+    }
+
+    pub fn close_other(&mut self) {
+        self.other = None;
+        self.transfer_widget = None;
+        self.diff_widget = None;
+    }
+
+    /// Compares this save with the second save.
+    pub fn compare_with_other(&mut self) -> Result<()> {
+        let other = self
+            .other
+            .as_ref()
+            .ok_or_else(|| anyhow!("No second save has been opened."))?;
+        self.diff_widget = Some(DiffWidget::new(SaveDiff::new(
+            &self.display_name,
+            &self.save,
+            &other.display_name,
+            &other.save,
+        )));
+        Ok(())
+    }
+
+    pub fn close_diff(&mut self) {
+        self.diff_widget = None;
+    }
+
+    pub fn export_diff(&self, format: DiffFormat) -> Result<String> {
+        self.diff_widget
+            .as_ref()
+            .ok_or_else(|| anyhow!("The saves have not been compared yet."))?
+            .get_diff()
+            .export(format)
+    }
+
+    pub fn set_transfer_direction(&mut self, direction: TransferDirection) {
+        if let Some(transfer_widget) = &mut self.transfer_widget {
+            transfer_widget.set_direction(direction);
+        }
+    }
+
+    pub fn set_transfer_source(&mut self, slot: SlotOption) {
+        if let Some(transfer_widget) = &mut self.transfer_widget {
+            transfer_widget.set_source_slot(slot);
         }
     }
 
+    pub fn set_transfer_target(&mut self, slot: SlotOption) {
+        if let Some(transfer_widget) = &mut self.transfer_widget {
+            transfer_widget.set_target_slot(slot);
+        }
+    }
+
+    pub fn get_backup(&self) -> Option<&SaveBackup> {
+        self.backup.as_ref()
+    }
+
+    pub fn export_backup(&mut self, target: &Path) -> Result<()> {
+        let backup = self
+            .backup
+            .as_ref()
+            .ok_or_else(|| anyhow!("This save has not been opened from a backup."))?;
+        let path = backup.export(target)?;
+        self.backup_message = Some(format!(
+            "Backup has been exported to {}",
+            path.to_string_lossy()
+        ));
+        Ok(())
+    }
+
+    /// Writes a course as zip file, which can be uploaded to SMMDB.
+    pub fn export_course(&self, index: u8, target: &Path) -> Result<()> {
+        let course = self.get_course(index)?;
+        std::fs::write(target, course.as_zip()?)?;
+        Ok(())
+    }
+
+    /// Compares the save folder on disk with the state it had when it was loaded or last written
+    /// by us.
+    pub fn check_external_modification(&mut self) -> bool {
+        if !self.is_modified_externally {
+            if let Ok(fingerprint) = SaveFingerprint::from_dir(&self.location) {
+                self.is_modified_externally = self.fingerprint.as_ref() != Some(&fingerprint);
+            }
+        }
+        self.is_modified_externally
+    }
+
+    /// Accepts the external modification, so that our next write overwrites it.
+    pub fn ignore_external_modification(&mut self) {
+        self.is_modified_externally = false;
+        self.update_fingerprint();
+    }
+
+    pub fn set_running_emulators(&mut self, running_emulators: Vec<RunningEmulator>) {
+        self.running_emulators = running_emulators;
+    }
+
+    fn check_running_emulators(&mut self) {
+        if !self.read_only {
+            self.running_emulators = find_running_emulators(&self.location);
+        }
+    }
+
+    /// Reloads the save from disk and writes the slots, which have been changed by us since
+    /// loading, on top of it. All other slots keep the content written by the other program.
+    ///
+    /// Returns the SMMDB ids of the merged save.
+    pub async fn merge_external_modification(
+        &mut self,
+        course_responses: &HashMap<String, Course2Response>,
+    ) -> Result<Vec<String>> {
+        self.ensure_unlocked()?;
+        let (mut smmdb_save, fingerprint) = save_watcher::load_save(self.location.clone()).await?;
+        for index in self.changed_slots.iter().copied() {
+            if let Some(Some(_)) = smmdb_save.get_own_courses().get(index as usize) {
+                smmdb_save.remove_course(index)?;
+            }
+            if let Ok(course) = self.get_course(index) {
+                smmdb_save.add_course(index, course)?;
+            }
+        }
+        if SaveFingerprint::from_dir(&self.location).ok() != fingerprint {
+            return Err(anyhow!(
+                "The save file has been modified again while merging. Please try again."
+            ));
+        }
+        self.save = smmdb_save;
+        self.save
+            .save()
+            .await
+            .map_err(|err| -> anyhow::Error { err.into() })?;
+        self.is_modified_externally = false;
+        self.update_fingerprint();
+        self.generate_course_panels(course_responses);
+        Ok(self.installed_courses.keys().cloned().collect())
+    }
+
+    /// Fails if the save must not be written, because it is read-only or used by an emulator.
+    fn ensure_unlocked(&mut self) -> Result<()> {
+        if self.read_only {
+            return Err(anyhow!("This save has been opened in read-only mode."));
+        }
+        self.check_running_emulators();
+        if let Some(emulator) = self
+            .running_emulators
+            .iter()
+            .find(|emulator| emulator.uses_save)
+        {
+            return Err(anyhow!(
+                "{} is currently using this save. Please close the emulator first.",
+                emulator
+            ));
+        }
+        Ok(())
+    }
+
+    fn ensure_writable(&mut self) -> Result<()> {
+        self.ensure_unlocked()?;
+        if self.check_external_modification() {
+            Err(anyhow!(
+                "The save file has been modified by another program. Please reload it first."
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn update_fingerprint(&mut self) {
+        self.fingerprint = SaveFingerprint::from_dir(&self.location).ok();
+    }
+
     pub fn set_course_response(&mut self, courses: &HashMap<String, Course2Response>) {
         self.save_widget.set_course_response(courses);
         self.generate_course_panels(courses);
+        if let Some(other) = &mut self.other {
+            other.set_course_response(courses);
+        }
     }
 
     pub fn set_smmdb_tab(&mut self, tab: SmmdbTab) {
         self.smmdb_widget.set_smmdb_tab(tab);
     }
 
-    pub fn view<'a>(&'a mut self, state: &AppState, smmdb: &'a mut Smmdb) -> Element<Message> {
-        Row::new()
-            .push(
-                self.save_widget
-                    .view(state, &self.display_name, smmdb.get_user()),
+    pub fn get_save_filter_mut(&mut self) -> &mut SaveFilter {
+        self.save_widget.get_filter_mut()
+    }
+
+    pub fn reset_save_filter(&mut self) {
+        self.save_widget.reset_filter();
+    }
+
+    pub fn focus_slot(&mut self, index: usize) {
+        self.save_widget.focus_slot(index);
+    }
+
+    pub fn get_installed_courses(&self) -> &HashMap<String, Vec<usize>> {
+        &self.installed_courses
+    }
+
+    /// Shows the course updates, while this save is being checked for them.
+    pub fn open_course_updates(&mut self) {
+        self.updates_widget = Some(UpdatesWidget::new());
+    }
+
+    pub fn set_course_updates(&mut self, updates: Vec<CourseUpdate>) {
+        self.updates_widget
+            .get_or_insert_with(UpdatesWidget::new)
+            .set_updates(updates);
+    }
+
+    pub fn start_course_updates(&mut self) -> Option<CourseUpdate> {
+        self.updates_widget.as_mut()?.start_update_all()
+    }
+
+    /// Marks the course in the given slot as updated and returns the next update to install.
+    pub fn finish_course_update(&mut self, slot: usize) -> Option<CourseUpdate> {
+        self.updates_widget.as_mut()?.finish_update(slot)
+    }
+
+    /// Stops installing all updates, e.g. because an update failed.
+    pub fn stop_course_updates(&mut self) {
+        if let Some(updates_widget) = &mut self.updates_widget {
+            updates_widget.stop_update_all();
+        }
+    }
+
+    pub fn close_course_updates(&mut self) {
+        self.updates_widget = None;
+    }
+
+    pub fn get_course_panel_mut(&mut self, index: usize) -> Option<&mut CoursePanel> {
+        self.save_widget.get_course_panel_mut(index)
+    }
+
+    pub fn view<'a>(
+        &'a mut self,
+        state: &AppState,
+        error_state: &AppErrorState,
+        smmdb: &'a mut Smmdb,
+    ) -> Element<Message> {
+        let widgets = Row::new().push(self.save_widget.view(
+            state,
+            &self.display_name,
+            smmdb.get_user(),
+            self.read_only,
+        ));
+        let widgets: Element<Message> = if let Some(other) = &mut self.other {
+            let widgets = widgets.push(other.save_widget.view(
+                state,
+                &other.display_name,
+                smmdb.get_user(),
+                true,
+            ));
+            let mut content = Column::new();
+            if let Some(transfer_widget) = &mut self.transfer_widget {
+                content = content.push(transfer_widget.view(state));
+            }
+            if let Some(diff_widget) = &mut self.diff_widget {
+                content = content.push(diff_widget.view());
+            }
+            content.push(widgets).into()
+        } else {
+            let mut open_other_button = Button::new(
+                &mut self.open_other_state,
+                Text::new("Open second save side by side"),
+            )
+            .padding(BUTTON_PADDING)
+            .style(DefaultButtonStyle);
+            let mut open_other_zip_button = Button::new(
+                &mut self.open_other_zip_state,
+                Text::new("Open zip backup side by side"),
             )
-            .push(self.smmdb_widget.view(state, smmdb))
-            .into()
+            .padding(BUTTON_PADDING)
+            .style(DefaultButtonStyle);
+            let mut check_updates_button = Button::new(
+                &mut self.check_updates_state,
+                Text::new("Check for course updates"),
+            )
+            .padding(BUTTON_PADDING)
+            .style(DefaultButtonStyle);
+            if state != &AppState::Loading {
+                open_other_button = open_other_button.on_press(Message::OpenSecondSave(false));
+                open_other_zip_button =
+                    open_other_zip_button.on_press(Message::OpenSecondSave(true));
+                check_updates_button = check_updates_button.on_press(Message::CheckCourseUpdates);
+            }
+            let mut content = Column::new().push(
+                Row::new()
+                    .padding(CONTAINER_PADDING)
+                    .spacing(LIST_SPACING)
+                    .push(Space::with_width(Length::Fill))
+                    .push(check_updates_button)
+                    .push(open_other_button)
+                    .push(open_other_zip_button),
+            );
+            if let Some(updates_widget) = &mut self.updates_widget {
+                content = content.push(updates_widget.view(state, self.read_only));
+            }
+            content
+                .push(
+                    widgets.push(
+                        self.smmdb_widget
+                            .view(state, smmdb, &self.installed_courses),
+                    ),
+                )
+                .into()
+        };
+
+        let emulator_warning: Option<Element<Message>> = if self.running_emulators.is_empty() {
+            None
+        } else {
+            let emulators = self
+                .running_emulators
+                .iter()
+                .map(|emulator| emulator.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            let text = if self
+                .running_emulators
+                .iter()
+                .any(|emulator| emulator.uses_save)
+            {
+                format!(
+                    "{} is using this save. Changes are disabled until the emulator has been closed.",
+                    emulators
+                )
+            } else {
+                format!(
+                    "{} is running. Please make sure it does not use this save before changing it.",
+                    emulators
+                )
+            };
+            Some(
+                Row::new()
+                    .padding(CONTAINER_PADDING)
+                    .push(Text::new(text).font(HELVETICA_BOLD).color(COLOR_DARK_RED))
+                    .into(),
+            )
+        };
+        let widgets: Element<Message> = if let Some(emulator_warning) = emulator_warning {
+            Column::new().push(emulator_warning).push(widgets).into()
+        } else {
+            widgets
+        };
+        let widgets: Element<Message> = if let Some(backup) = &self.backup {
+            let mut info = Column::new().spacing(LIST_SPACING).push(Text::new(format!(
+                "Editing a {} backup of {}. Export the backup to restore it on your console.",
+                backup.get_format(),
+                backup.get_source().to_string_lossy()
+            )));
+            if let Some(backup_message) = &self.backup_message {
+                info = info.push(Text::new(backup_message).color(TEXT_HELP_COLOR));
+            }
+            let backup_row = Row::new()
+                .padding(CONTAINER_PADDING)
+                .align_items(Align::Center)
+                .push(info)
+                .push(Space::with_width(Length::Fill))
+                .push(
+                    Button::new(&mut self.export_state, Text::new("Export backup"))
+                        .padding(BUTTON_PADDING)
+                        .style(DefaultButtonStyle)
+                        .on_press(Message::ExportBackup),
+                );
+            Column::new().push(backup_row).push(widgets).into()
+        } else {
+            widgets
+        };
+
+        if self.is_modified_externally {
+            let mut warning = Row::new()
+                .padding(CONTAINER_PADDING)
+                .align_items(Align::Center)
+                .push(
+                    Text::new(
+                        "Your save file has been modified by another program since it was loaded.",
+                    )
+                    .font(HELVETICA_BOLD)
+                    .color(COLOR_DARK_RED),
+                )
+                .push(Space::with_width(Length::Fill))
+                .push(
+                    Button::new(&mut self.reload_state, Text::new("Reload"))
+                        .padding(BUTTON_PADDING)
+                        .style(DefaultButtonStyle)
+                        .on_press(Message::ReloadSave),
+                );
+            if !self.read_only && !self.changed_slots.is_empty() {
+                warning = warning.push(Space::with_width(Length::Units(12))).push(
+                    Button::new(&mut self.merge_state, Text::new("Merge my changes"))
+                        .padding(BUTTON_PADDING)
+                        .style(DefaultButtonStyle)
+                        .on_press(Message::MergeSaveModification),
+                );
+            }
+            if !self.read_only {
+                warning = warning.push(Space::with_width(Length::Units(12))).push(
+                    Button::new(&mut self.overwrite_state, Text::new("Keep my version"))
+                        .padding(BUTTON_PADDING)
+                        .style(DefaultButtonDangerStyle)
+                        .on_press(Message::IgnoreSaveModification),
+                );
+            }
+            Column::new().push(warning).push(widgets).into()
+        } else if let AppErrorState::Some(err) = error_state {
+            Column::new()
+                .push(
+                    Row::new()
+                        .padding(CONTAINER_PADDING)
+                        .push(Text::new(err).font(HELVETICA_BOLD).color(COLOR_DARK_RED)),
+                )
+                .push(widgets)
+                .into()
+        } else {
+            widgets.into()
+        }
     }
 
     pub async fn swap_courses(
@@ -55,11 +542,15 @@ impl SavePage {
         second: u8,
         course_responses: &HashMap<String, Course2Response>,
     ) -> Result<()> {
+        self.ensure_writable()?;
         self.save.swap_course(first, second)?;
         self.save
             .save()
             .await
             .map_err(|err| -> anyhow::Error { err.into() })?;
+        self.changed_slots.insert(first);
+        self.changed_slots.insert(second);
+        self.update_fingerprint();
         self.generate_course_panels(course_responses);
         Ok(())
     }
@@ -70,11 +561,34 @@ impl SavePage {
         course: smmdb_lib::Course2,
         course_responses: &HashMap<String, Course2Response>,
     ) -> Result<()> {
+        self.ensure_writable()?;
         self.save.add_course(index, course)?;
         self.save
             .save()
             .await
             .map_err(|err| -> anyhow::Error { err.into() })?;
+        self.changed_slots.insert(index);
+        self.update_fingerprint();
+        self.generate_course_panels(course_responses);
+        Ok(())
+    }
+
+    /// Replaces the course of an occupied slot, e.g. with a newer version from SMMDB.
+    pub async fn replace_course(
+        &mut self,
+        index: u8,
+        course: smmdb_lib::Course2,
+        course_responses: &HashMap<String, Course2Response>,
+    ) -> Result<()> {
+        self.ensure_writable()?;
+        self.save.remove_course(index)?;
+        self.save.add_course(index, course)?;
+        self.save
+            .save()
+            .await
+            .map_err(|err| -> anyhow::Error { err.into() })?;
+        self.changed_slots.insert(index);
+        self.update_fingerprint();
         self.generate_course_panels(course_responses);
         Ok(())
     }
@@ -84,17 +598,178 @@ impl SavePage {
         index: u8,
         course_responses: &HashMap<String, Course2Response>,
     ) -> Result<()> {
+        self.ensure_writable()?;
+        self.save.remove_course(index)?;
+        self.save
+            .save()
+            .await
+            .map_err(|err| -> anyhow::Error { err.into() })?;
+        self.changed_slots.insert(index);
+        self.update_fingerprint();
+        self.generate_course_panels(course_responses);
+        Ok(())
+    }
+
+    pub async fn edit_course(
+        &mut self,
+        index: u8,
+        title: &str,
+        description: &str,
+        course_responses: &HashMap<String, Course2Response>,
+    ) -> Result<()> {
+        let course = self.get_course(index)?;
+        let course = course_edit::set_course_metadata(&course, title, description)?;
+        self.ensure_writable()?;
         self.save.remove_course(index)?;
+        self.save.add_course(index, course)?;
         self.save
             .save()
             .await
             .map_err(|err| -> anyhow::Error { err.into() })?;
+        self.changed_slots.insert(index);
+        self.update_fingerprint();
         self.generate_course_panels(course_responses);
         Ok(())
     }
 
+    /// Copies or moves the selected course of the transfer widget between both saves.
+    ///
+    /// The course is copied as is, so that its SMMDB id is preserved.
+    pub async fn transfer_course(
+        &mut self,
+        is_move: bool,
+        course_responses: &HashMap<String, Course2Response>,
+    ) -> Result<()> {
+        let transfer_widget = self
+            .transfer_widget
+            .as_ref()
+            .ok_or_else(|| anyhow!("No second save has been opened."))?;
+        let (source_index, target_index) = transfer_widget
+            .get_selection()
+            .ok_or_else(|| anyhow!("Please select a source and a target slot."))?;
+        let direction = transfer_widget.get_direction();
+        let mut other = self
+            .other
+            .take()
+            .ok_or_else(|| anyhow!("No second save has been opened."))?;
+        let res = match direction {
+            TransferDirection::LeftToRight => {
+                self.transfer_course_to(
+                    &mut other,
+                    source_index,
+                    target_index,
+                    is_move,
+                    course_responses,
+                )
+                .await
+            }
+            TransferDirection::RightToLeft => {
+                other
+                    .transfer_course_to(self, source_index, target_index, is_move, course_responses)
+                    .await
+            }
+        };
+        if let Some(transfer_widget) = &mut self.transfer_widget {
+            transfer_widget.set_saves(&self.save, &other.save);
+        }
+        self.other = Some(other);
+        res
+    }
+
+    async fn transfer_course_to(
+        &mut self,
+        target: &mut SavePage,
+        source_index: u8,
+        target_index: u8,
+        is_move: bool,
+        course_responses: &HashMap<String, Course2Response>,
+    ) -> Result<()> {
+        let course = self.get_course(source_index)?;
+        if let Some(Some(_)) = target.save.get_own_courses().get(target_index as usize) {
+            return Err(anyhow!(
+                "Course slot {} of the target save is not empty.",
+                target_index + 1
+            ));
+        }
+        target.ensure_writable()?;
+        if is_move {
+            self.ensure_writable()?;
+        }
+        target.save.add_course(target_index, course.clone())?;
+        target
+            .save
+            .save()
+            .await
+            .map_err(|err| -> anyhow::Error { err.into() })?;
+        target.changed_slots.insert(target_index);
+        target.update_fingerprint();
+        target.generate_course_panels(course_responses);
+
+        if is_move {
+            if let Err(err) = self.remove_course_after_copy(source_index).await {
+                // The course is restored in memory, since the source save has not been written.
+                if !self.has_course(source_index) {
+                    self.save.add_course(source_index, course)?;
+                }
+                return Err(anyhow!(
+                    "The course has been copied to slot {} of the target save, but it could not \
+                    be removed from slot {}, so it has not been moved: {}",
+                    target_index + 1,
+                    source_index + 1,
+                    err
+                ));
+            }
+            self.changed_slots.insert(source_index);
+            self.update_fingerprint();
+            self.generate_course_panels(course_responses);
+        }
+        Ok(())
+    }
+
+    async fn remove_course_after_copy(&mut self, index: u8) -> Result<()> {
+        self.save.remove_course(index)?;
+        self.save
+            .save()
+            .await
+            .map_err(|err| -> anyhow::Error { err.into() })?;
+        Ok(())
+    }
+
+    pub fn has_course(&self, index: u8) -> bool {
+        matches!(
+            self.save.get_own_courses().get(index as usize),
+            Some(Some(_))
+        )
+    }
+
+    fn get_course(&self, index: u8) -> Result<smmdb_lib::Course2> {
+        match self.save.get_own_courses().get(index as usize) {
+            Some(Some(course)) => match &**course {
+                CourseEntry::SavedCourse(course) => Ok(course.get_course().clone()),
+                CourseEntry::CorruptedCourse(_) => Err(anyhow!("Course is corrupted")),
+            },
+            _ => Err(anyhow!("Course slot is empty")),
+        }
+    }
+
     fn generate_course_panels(&mut self, course_responses: &HashMap<String, Course2Response>) {
         self.save_widget
             .regenerate_course_panels(&self.save, course_responses);
+        self.installed_courses = get_installed_courses(&self.save);
+        if let (Some(transfer_widget), Some(other)) = (&mut self.transfer_widget, &self.other) {
+            transfer_widget.set_saves(&self.save, &other.save);
+        }
+    }
+}
+
+fn get_installed_courses(save: &smmdb_lib::Save) -> HashMap<String, Vec<usize>> {
+    let mut installed_courses: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, course) in save.get_own_courses().iter().enumerate() {
+        if let Some(CourseEntry::SavedCourse(course)) = course.as_deref() {
+            if let Some(smmdb_id) = course.get_course().get_smmdb_id() {
+                installed_courses.entry(smmdb_id).or_default().push(index);
+            }
+        }
     }
+    installed_courses
 }
@@ -103,7 +103,7 @@ impl SettingsPage {
                 .push(
                     Button::new(&mut self.save, Text::new("Save and close"))
                         .style(DefaultButtonStyle)
-                        .on_press(Message::TrySaveSettings(self.settings.clone())),
+                        .on_press(Message::TrySaveSettings(self.settings.apikey.clone())),
                 )
                 .push(Space::with_width(Length::Units(12)));
         }
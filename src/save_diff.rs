@@ -0,0 +1,249 @@
+use anyhow::Result;
+use serde::Serialize;
+use smmdb_lib::CourseEntry;
+use std::fmt::{self, Display, Write};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DiffFormat {
+    Json,
+    Markdown,
+}
+
+impl DiffFormat {
+    pub fn get_extension(&self) -> &'static str {
+        match self {
+            DiffFormat::Json => "json",
+            DiffFormat::Markdown => "md",
+        }
+    }
+}
+
+/// A course of one of the compared saves.
+#[derive(Clone, Debug, Serialize)]
+pub struct DiffCourse {
+    /// Slot number as shown in the game, starting at 1.
+    pub slot: u16,
+    pub title: String,
+    pub smmdb_id: Option<String>,
+    /// CRC32 of the course data, which identifies courses with the same content. It is stable
+    /// across releases, so that exported comparisons can be compared with each other.
+    pub hash: String,
+}
+
+impl Display for DiffCourse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"{}\"", self.title)?;
+        if let Some(smmdb_id) = &self.smmdb_id {
+            write!(f, " (SMMDB {})", smmdb_id)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DiffEntry {
+    Added {
+        course: DiffCourse,
+    },
+    Removed {
+        course: DiffCourse,
+    },
+    /// Same content in another slot.
+    Moved {
+        from: DiffCourse,
+        to: DiffCourse,
+    },
+    /// Same SMMDB id, but different content.
+    Changed {
+        from: DiffCourse,
+        to: DiffCourse,
+    },
+}
+
+impl Display for DiffEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiffEntry::Added { course } => write!(f, "Added {} in slot {}", course, course.slot),
+            DiffEntry::Removed { course } => {
+                write!(f, "Removed {} from slot {}", course, course.slot)
+            }
+            DiffEntry::Moved { from, to } => write!(
+                f,
+                "Moved {} from slot {} to slot {}",
+                from, from.slot, to.slot
+            ),
+            DiffEntry::Changed { from, to } => {
+                if from.slot == to.slot {
+                    write!(f, "Changed {} in slot {}", to, to.slot)
+                } else {
+                    write!(
+                        f,
+                        "Changed {} and moved it from slot {} to slot {}",
+                        to, from.slot, to.slot
+                    )
+                }
+            }
+        }
+    }
+}
+
+/// Differences from the `left` save to the `right` save.
+#[derive(Clone, Debug, Serialize)]
+pub struct SaveDiff {
+    pub left: String,
+    pub right: String,
+    pub unchanged: usize,
+    pub entries: Vec<DiffEntry>,
+}
+
+impl SaveDiff {
+    /// Compares two saves.
+    ///
+    /// Courses are matched by their content hash first, so that moved courses are detected.
+    /// Remaining courses with the same SMMDB id are reported as changed.
+    pub fn new(
+        left_name: &str,
+        left: &smmdb_lib::Save,
+        right_name: &str,
+        right: &smmdb_lib::Save,
+    ) -> SaveDiff {
+        let mut left_courses = get_courses(left);
+        let mut right_courses = get_courses(right);
+        let mut unchanged = 0;
+        let mut entries = vec![];
+
+        // Courses which did not change are matched first, so that duplicates are not reported as
+        // moved.
+        left_courses.retain(|course| {
+            if let Some(index) = right_courses
+                .iter()
+                .position(|other| other.slot == course.slot && other.hash == course.hash)
+            {
+                right_courses.remove(index);
+                unchanged += 1;
+                false
+            } else {
+                true
+            }
+        });
+        left_courses.retain(|course| {
+            if let Some(index) = right_courses
+                .iter()
+                .position(|other| other.hash == course.hash)
+            {
+                entries.push(DiffEntry::Moved {
+                    from: course.clone(),
+                    to: right_courses.remove(index),
+                });
+                false
+            } else {
+                true
+            }
+        });
+        left_courses.retain(|course| {
+            let smmdb_id = match &course.smmdb_id {
+                Some(smmdb_id) => smmdb_id,
+                None => return true,
+            };
+            if let Some(index) = right_courses
+                .iter()
+                .position(|other| other.smmdb_id.as_ref() == Some(smmdb_id))
+            {
+                entries.push(DiffEntry::Changed {
+                    from: course.clone(),
+                    to: right_courses.remove(index),
+                });
+                false
+            } else {
+                true
+            }
+        });
+        entries.extend(
+            left_courses
+                .into_iter()
+                .map(|course| DiffEntry::Removed { course }),
+        );
+        entries.extend(
+            right_courses
+                .into_iter()
+                .map(|course| DiffEntry::Added { course }),
+        );
+        entries.sort_by_key(|entry| match entry {
+            DiffEntry::Added { course } | DiffEntry::Removed { course } => course.slot,
+            DiffEntry::Moved { from, .. } | DiffEntry::Changed { from, .. } => from.slot,
+        });
+
+        SaveDiff {
+            left: left_name.to_string(),
+            right: right_name.to_string(),
+            unchanged,
+            entries,
+        }
+    }
+
+    pub fn export(&self, format: DiffFormat) -> Result<String> {
+        match format {
+            DiffFormat::Json => Ok(serde_json::to_string_pretty(self)?),
+            DiffFormat::Markdown => self.to_markdown(),
+        }
+    }
+
+    fn to_markdown(&self) -> Result<String> {
+        let mut markdown = String::new();
+        writeln!(markdown, "# Save comparison")?;
+        writeln!(markdown)?;
+        writeln!(markdown, "- From: `{}`", self.left)?;
+        writeln!(markdown, "- To: `{}`", self.right)?;
+        writeln!(markdown, "- Unchanged courses: {}", self.unchanged)?;
+        writeln!(markdown)?;
+        if self.entries.is_empty() {
+            writeln!(markdown, "No differences found.")?;
+            return Ok(markdown);
+        }
+        writeln!(
+            markdown,
+            "| Change | Course | SMMDB id | From slot | To slot |"
+        )?;
+        writeln!(markdown, "| --- | --- | --- | --- | --- |")?;
+        for entry in self.entries.iter() {
+            let (kind, course, from, to) = match entry {
+                DiffEntry::Added { course } => ("Added", course, None, Some(course.slot)),
+                DiffEntry::Removed { course } => ("Removed", course, Some(course.slot), None),
+                DiffEntry::Moved { from, to } => ("Moved", to, Some(from.slot), Some(to.slot)),
+                DiffEntry::Changed { from, to } => ("Changed", to, Some(from.slot), Some(to.slot)),
+            };
+            writeln!(
+                markdown,
+                "| {} | {} | {} | {} | {} |",
+                kind,
+                course.title.replace('|', "\\|"),
+                course.smmdb_id.as_deref().unwrap_or("-"),
+                from.map(|slot| slot.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+                to.map(|slot| slot.to_string())
+                    .unwrap_or_else(|| "-".to_string()),
+            )?;
+        }
+        Ok(markdown)
+    }
+}
+
+fn get_courses(save: &smmdb_lib::Save) -> Vec<DiffCourse> {
+    save.get_own_courses()
+        .iter()
+        .enumerate()
+        .filter_map(|(index, course)| match course.as_deref() {
+            Some(CourseEntry::SavedCourse(course)) => {
+                let course = course.get_course();
+                Some(DiffCourse {
+                    slot: index as u16 + 1,
+                    title: course.get_course().get_header().get_title().to_string(),
+                    smmdb_id: course.get_smmdb_id(),
+                    hash: format!("{:08x}", crc32fast::hash(&course.get_course_data()[..])),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
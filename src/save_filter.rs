@@ -0,0 +1,290 @@
+use crate::smmdb::{Course2Response, Difficulty};
+
+use smmdb_lib::{
+    proto::SMM2Course::{SMM2CourseArea_CourseTheme, SMM2CourseHeader_GameStyle},
+    CourseEntry,
+};
+use std::fmt;
+
+#[derive(Clone, Debug, Default)]
+pub struct SaveFilter {
+    pub text: String,
+    pub game_style: Option<GameStyle>,
+    pub theme: Option<CourseTheme>,
+    pub uploaded: Option<UploadedFilter>,
+    pub difficulty: Option<Difficulty>,
+    pub slot: Option<SlotFilter>,
+}
+
+impl SaveFilter {
+    pub fn is_active(&self) -> bool {
+        !self.text.is_empty()
+            || self.game_style.is_some()
+            || self.theme.is_some()
+            || self.uploaded.is_some()
+            || self.difficulty.is_some()
+            || self.slot.is_some()
+    }
+
+    fn has_course_filter(&self) -> bool {
+        !self.text.is_empty()
+            || self.game_style.is_some()
+            || self.theme.is_some()
+            || self.uploaded.is_some()
+            || self.difficulty.is_some()
+    }
+
+    pub fn set_text(&mut self, text: String) {
+        self.text = text;
+    }
+
+    pub fn set_game_style(&mut self, game_style: GameStyle) {
+        self.game_style = if let GameStyle::Unset = game_style {
+            None
+        } else {
+            Some(game_style)
+        };
+    }
+
+    pub fn set_theme(&mut self, theme: CourseTheme) {
+        self.theme = if let CourseTheme::Unset = theme {
+            None
+        } else {
+            Some(theme)
+        };
+    }
+
+    pub fn set_uploaded(&mut self, uploaded: UploadedFilter) {
+        self.uploaded = if let UploadedFilter::Unset = uploaded {
+            None
+        } else {
+            Some(uploaded)
+        };
+    }
+
+    pub fn set_difficulty(&mut self, difficulty: Difficulty) {
+        self.difficulty = if let Difficulty::Unset = difficulty {
+            None
+        } else {
+            Some(difficulty)
+        };
+    }
+
+    pub fn set_slot(&mut self, slot: SlotFilter) {
+        self.slot = if let SlotFilter::Unset = slot {
+            None
+        } else {
+            Some(slot)
+        };
+    }
+
+    /// Checks whether a save slot should be visible with the current filter.
+    ///
+    /// Empty slots only match, if no course specific filter is set.
+    pub fn matches(
+        &self,
+        course: Option<&CourseEntry>,
+        course_response: Option<&Course2Response>,
+    ) -> bool {
+        let course = match (course, &self.slot) {
+            (None, Some(SlotFilter::Filled)) => return false,
+            (None, _) => return !self.has_course_filter(),
+            (Some(_), Some(SlotFilter::Empty)) => return false,
+            (Some(CourseEntry::SavedCourse(course)), _) => course.get_course(),
+            (Some(CourseEntry::CorruptedCourse(_)), _) => return !self.has_course_filter(),
+        };
+        let smm2_course = course.get_course();
+        let header = smm2_course.get_header();
+
+        if !self.text.is_empty() {
+            let text = self.text.to_lowercase();
+            if !header.get_title().to_lowercase().contains(&text)
+                && !header.get_description().to_lowercase().contains(&text)
+            {
+                return false;
+            }
+        }
+        if let Some(game_style) = &self.game_style {
+            if *game_style != header.get_game_style().into() {
+                return false;
+            }
+        }
+        if let Some(theme) = &self.theme {
+            if *theme != smm2_course.get_course_area().get_course_theme().into() {
+                return false;
+            }
+        }
+        if let Some(uploaded) = &self.uploaded {
+            let is_uploaded = course.get_smmdb_id().is_some();
+            match uploaded {
+                UploadedFilter::Uploaded if !is_uploaded => return false,
+                UploadedFilter::NotUploaded if is_uploaded => return false,
+                _ => {}
+            }
+        }
+        if let Some(difficulty) = &self.difficulty {
+            if course_response.and_then(|res| res.get_difficulty()) != Some(difficulty) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GameStyle {
+    Unset,
+    SuperMarioBros,
+    SuperMarioBros3,
+    SuperMarioWorld,
+    NewSuperMarioBrosU,
+    SuperMario3DWorld,
+}
+
+impl GameStyle {
+    pub const ALL: [GameStyle; 6] = [
+        GameStyle::Unset,
+        GameStyle::SuperMarioBros,
+        GameStyle::SuperMarioBros3,
+        GameStyle::SuperMarioWorld,
+        GameStyle::NewSuperMarioBrosU,
+        GameStyle::SuperMario3DWorld,
+    ];
+}
+
+impl From<SMM2CourseHeader_GameStyle> for GameStyle {
+    fn from(game_style: SMM2CourseHeader_GameStyle) -> Self {
+        match game_style {
+            SMM2CourseHeader_GameStyle::M1 => GameStyle::SuperMarioBros,
+            SMM2CourseHeader_GameStyle::M3 => GameStyle::SuperMarioBros3,
+            SMM2CourseHeader_GameStyle::MW => GameStyle::SuperMarioWorld,
+            SMM2CourseHeader_GameStyle::WU => GameStyle::NewSuperMarioBrosU,
+            SMM2CourseHeader_GameStyle::W3 => GameStyle::SuperMario3DWorld,
+        }
+    }
+}
+
+impl fmt::Display for GameStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GameStyle::Unset => write!(f, ""),
+            GameStyle::SuperMarioBros => write!(f, "Super Mario Bros."),
+            GameStyle::SuperMarioBros3 => write!(f, "Super Mario Bros. 3"),
+            GameStyle::SuperMarioWorld => write!(f, "Super Mario World"),
+            GameStyle::NewSuperMarioBrosU => write!(f, "New Super Mario Bros. U"),
+            GameStyle::SuperMario3DWorld => write!(f, "Super Mario 3D World"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CourseTheme {
+    Unset,
+    Ground,
+    Underground,
+    Castle,
+    Airship,
+    Underwater,
+    GhostHouse,
+    Snow,
+    Desert,
+    Sky,
+    Forest,
+}
+
+impl CourseTheme {
+    pub const ALL: [CourseTheme; 11] = [
+        CourseTheme::Unset,
+        CourseTheme::Ground,
+        CourseTheme::Underground,
+        CourseTheme::Castle,
+        CourseTheme::Airship,
+        CourseTheme::Underwater,
+        CourseTheme::GhostHouse,
+        CourseTheme::Snow,
+        CourseTheme::Desert,
+        CourseTheme::Sky,
+        CourseTheme::Forest,
+    ];
+}
+
+impl From<SMM2CourseArea_CourseTheme> for CourseTheme {
+    fn from(theme: SMM2CourseArea_CourseTheme) -> Self {
+        match theme {
+            SMM2CourseArea_CourseTheme::GROUND => CourseTheme::Ground,
+            SMM2CourseArea_CourseTheme::UNDERGROUND => CourseTheme::Underground,
+            SMM2CourseArea_CourseTheme::CASTLE => CourseTheme::Castle,
+            SMM2CourseArea_CourseTheme::AIRSHIP => CourseTheme::Airship,
+            SMM2CourseArea_CourseTheme::UNDERWATER => CourseTheme::Underwater,
+            SMM2CourseArea_CourseTheme::GHOST_HOUSE => CourseTheme::GhostHouse,
+            SMM2CourseArea_CourseTheme::SNOW => CourseTheme::Snow,
+            SMM2CourseArea_CourseTheme::DESERT => CourseTheme::Desert,
+            SMM2CourseArea_CourseTheme::SKY => CourseTheme::Sky,
+            SMM2CourseArea_CourseTheme::FOREST => CourseTheme::Forest,
+        }
+    }
+}
+
+impl fmt::Display for CourseTheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CourseTheme::Unset => write!(f, ""),
+            CourseTheme::Ground => write!(f, "Ground"),
+            CourseTheme::Underground => write!(f, "Underground"),
+            CourseTheme::Castle => write!(f, "Castle"),
+            CourseTheme::Airship => write!(f, "Airship"),
+            CourseTheme::Underwater => write!(f, "Underwater"),
+            CourseTheme::GhostHouse => write!(f, "Ghost House"),
+            CourseTheme::Snow => write!(f, "Snow"),
+            CourseTheme::Desert => write!(f, "Desert"),
+            CourseTheme::Sky => write!(f, "Sky"),
+            CourseTheme::Forest => write!(f, "Forest"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UploadedFilter {
+    Unset,
+    Uploaded,
+    NotUploaded,
+}
+
+impl UploadedFilter {
+    pub const ALL: [UploadedFilter; 3] = [
+        UploadedFilter::Unset,
+        UploadedFilter::Uploaded,
+        UploadedFilter::NotUploaded,
+    ];
+}
+
+impl fmt::Display for UploadedFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UploadedFilter::Unset => write!(f, ""),
+            UploadedFilter::Uploaded => write!(f, "Uploaded to SMMDB"),
+            UploadedFilter::NotUploaded => write!(f, "Not uploaded"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SlotFilter {
+    Unset,
+    Filled,
+    Empty,
+}
+
+impl SlotFilter {
+    pub const ALL: [SlotFilter; 3] = [SlotFilter::Unset, SlotFilter::Filled, SlotFilter::Empty];
+}
+
+impl fmt::Display for SlotFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SlotFilter::Unset => write!(f, ""),
+            SlotFilter::Filled => write!(f, "Filled slots"),
+            SlotFilter::Empty => write!(f, "Empty slots"),
+        }
+    }
+}
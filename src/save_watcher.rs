@@ -0,0 +1,66 @@
+use anyhow::Result;
+use std::{
+    collections::hash_map::DefaultHasher,
+    fs::{read, read_dir},
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+/// Loads a save together with the fingerprint of its folder.
+///
+/// The fingerprint is taken before the files are read, so that a write of another program while
+/// loading is reported as an external modification instead of going unnoticed.
+pub async fn load_save(location: PathBuf) -> Result<(smmdb_lib::Save, Option<SaveFingerprint>)> {
+    let fingerprint = SaveFingerprint::from_dir(&location).ok();
+    let smmdb_save = smmdb_lib::Save::new(location)
+        .await
+        .map_err(|err| -> anyhow::Error { err.into() })?;
+    Ok((smmdb_save, fingerprint))
+}
+
+/// Snapshot of the files inside a save folder.
+///
+/// Every file is tracked by its size and modification time. The content of `save.dat` is hashed
+/// additionally, since some emulators preserve the modification time when writing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SaveFingerprint {
+    files: Vec<FileFingerprint>,
+    save_hash: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct FileFingerprint {
+    name: String,
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl SaveFingerprint {
+    pub fn from_dir(path: &Path) -> Result<SaveFingerprint> {
+        let mut files = vec![];
+        for entry in read_dir(path)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+            files.push(FileFingerprint {
+                name: entry.file_name().to_string_lossy().into(),
+                len: metadata.len(),
+                modified: metadata.modified().ok(),
+            });
+        }
+        files.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let save_hash = if let Ok(save_data) = read(path.join("save.dat")) {
+            let mut hasher = DefaultHasher::new();
+            save_data.hash(&mut hasher);
+            Some(hasher.finish())
+        } else {
+            None
+        };
+
+        Ok(SaveFingerprint { files, save_hash })
+    }
+}
@@ -1,3 +1,8 @@
+use crate::{
+    smmdb::{FavoriteCourse, FollowedUploader, InstalledVersion, QueryParams, SavedSearch},
+    EmuType,
+};
+
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -9,6 +14,31 @@ use std::{
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
 pub struct Settings {
     pub apikey: Option<String>,
+    /// Additional directories in which emulator installations are searched.
+    #[serde(default)]
+    pub search_roots: Vec<PathBuf>,
+    /// Save folders which have been registered manually by the user.
+    #[serde(default)]
+    pub registered_saves: Vec<RegisteredSave>,
+    /// Query of the last SMMDB search, which is restored on launch.
+    #[serde(default)]
+    pub query_params: Option<QueryParams>,
+    #[serde(default)]
+    pub saved_searches: Vec<SavedSearch>,
+    #[serde(default)]
+    pub followed_uploaders: Vec<FollowedUploader>,
+    #[serde(default)]
+    pub favorites: Vec<FavoriteCourse>,
+    /// SMMDB versions of downloaded courses by save and slot.
+    #[serde(default)]
+    pub installed_course_versions: Vec<InstalledVersion>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RegisteredSave {
+    pub display_name: String,
+    pub location: PathBuf,
+    pub emu_type: EmuType,
 }
 
 impl Settings {
@@ -7,9 +7,10 @@ use reqwest::{header, Client};
 use serde::{Deserialize, Serialize};
 use smmdb_lib::{proto::SMM2Course::SMM2Course, SavedCourse};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     fmt,
     io::{self, ErrorKind},
+    path::{Path, PathBuf},
 };
 
 #[derive(Clone, Debug, Deserialize)]
@@ -43,6 +44,30 @@ pub struct Smmdb {
     apikey: Option<String>,
     user: Option<SmmdbUser>,
     query_params: QueryParams,
+    show_advanced_search: bool,
+    /// Course id or smmdb.net URL entered by the user.
+    course_lookup: String,
+    /// Total number of courses matching the current query, if already known.
+    total_count: Option<u32>,
+    /// Lower bound of the total number of courses, if counting has been given up.
+    min_total_count: Option<u32>,
+    /// Incremented whenever the query changes, so that outdated responses are ignored.
+    query_generation: u32,
+    page_input: String,
+    /// Appends the next page to `course_panels` instead of replacing it.
+    infinite_scroll: bool,
+    is_prefetching: bool,
+    saved_searches: Vec<SavedSearch>,
+    selected_search: Option<String>,
+    saved_search_name: String,
+    profile: Option<UploaderProfile>,
+    followed_uploaders: Vec<FollowedUploader>,
+    /// New courses of followed uploaders, which have not yet been marked as read.
+    inbox: IndexMap<String, SmmdbCoursePanel>,
+    favorites: Vec<FavoriteCourse>,
+    favorite_panels: IndexMap<String, SmmdbCoursePanel>,
+    are_favorites_loaded: bool,
+    installed_versions: Vec<InstalledVersion>,
     course_responses: HashMap<String, Course2Response>,
     course_panels: IndexMap<String, SmmdbCoursePanel>,
     own_query_params: QueryParams,
@@ -57,6 +82,24 @@ impl Smmdb {
             apikey,
             user: None,
             query_params: serde_json::from_str::<QueryParams>("{}").unwrap(),
+            show_advanced_search: false,
+            course_lookup: String::new(),
+            total_count: None,
+            min_total_count: None,
+            query_generation: 0,
+            page_input: String::new(),
+            infinite_scroll: false,
+            is_prefetching: false,
+            saved_searches: vec![],
+            selected_search: None,
+            saved_search_name: String::new(),
+            profile: None,
+            followed_uploaders: vec![],
+            inbox: IndexMap::new(),
+            favorites: vec![],
+            favorite_panels: IndexMap::new(),
+            are_favorites_loaded: false,
+            installed_versions: vec![],
             course_responses: HashMap::new(),
             course_panels: IndexMap::new(),
             own_query_params: serde_json::from_str::<QueryParams>("{}").unwrap(),
@@ -89,6 +132,8 @@ impl Smmdb {
                 self.course_responses.insert(smmdb_id, course_response);
             });
         if update_panels {
+            // A failed prefetch is only retried once other courses are shown.
+            self.is_prefetching = false;
             self.course_panels.clear();
             courses
                 .into_iter()
@@ -99,6 +144,78 @@ impl Smmdb {
         }
     }
 
+    /// Starts fetching the page after the loaded courses, if infinite scrolling is enabled.
+    ///
+    /// Returns the query for the next page and the current query generation.
+    pub fn prepare_prefetch(&mut self) -> Option<(QueryParams, u32)> {
+        if !self.infinite_scroll || self.is_prefetching || !self.can_load_more() {
+            return None;
+        }
+        self.is_prefetching = true;
+        let mut query_params = self.query_params.clone();
+        query_params.skip += self.course_panels.len() as u32;
+        Some((query_params, self.query_generation))
+    }
+
+    /// Appends a prefetched page and drops pages from the front to keep memory bounded.
+    ///
+    /// Returns the ids of the new courses, or `None` if the page is outdated.
+    pub fn append_courses(
+        &mut self,
+        generation: u32,
+        skip: u32,
+        courses: Vec<Course2Response>,
+    ) -> Option<Vec<String>> {
+        self.is_prefetching = false;
+        if generation != self.query_generation
+            || skip != self.query_params.skip + self.course_panels.len() as u32
+        {
+            return None;
+        }
+        if (courses.len() as u32) < self.query_params.limit {
+            self.total_count = Some(skip + courses.len() as u32);
+        }
+        let ids: Vec<String> = courses
+            .iter()
+            .map(|course| course.get_id().clone())
+            .collect();
+        self.set_courses(courses.clone(), false);
+        courses
+            .into_iter()
+            .map(SmmdbCoursePanel::new)
+            .for_each(|course| {
+                self.course_panels.insert(course.get_id().clone(), course);
+            });
+
+        let max_len = (MAX_LOADED_PAGES * self.query_params.limit) as usize;
+        while self.course_panels.len() > max_len {
+            let dropped = self.query_params.limit as usize;
+            self.course_panels.drain(..dropped);
+            self.query_params.skip += dropped as u32;
+        }
+        Some(ids)
+    }
+
+    pub fn can_load_more(&self) -> bool {
+        let loaded = self.query_params.skip + self.course_panels.len() as u32;
+        !self.course_panels.is_empty()
+            && self
+                .total_count
+                .map_or(true, |total_count| loaded < total_count)
+    }
+
+    pub fn is_infinite_scroll(&self) -> bool {
+        self.infinite_scroll
+    }
+
+    pub fn set_infinite_scroll(&mut self, infinite_scroll: bool) {
+        self.infinite_scroll = infinite_scroll;
+    }
+
+    pub fn is_prefetching(&self) -> bool {
+        self.is_prefetching
+    }
+
     pub fn delete_course_response(&mut self, id: String) {
         self.course_responses.remove(&id);
         self.own_course_responses.remove(&id);
@@ -133,10 +250,310 @@ impl Smmdb {
             course_panel.set_thumbnail(thumbnail.clone());
         }
         if let Some(course_panel) = self.own_course_panels.get_mut(id) {
+            course_panel.set_thumbnail(thumbnail.clone());
+        }
+        if let Some(course_panel) = self.inbox.get_mut(id) {
+            course_panel.set_thumbnail(thumbnail.clone());
+        }
+        if let Some(course_panel) = self.favorite_panels.get_mut(id) {
+            course_panel.set_thumbnail(thumbnail.clone());
+        }
+        if let Some(course_panel) = self
+            .profile
+            .as_mut()
+            .and_then(|profile| profile.course_panels.get_mut(id))
+        {
             course_panel.set_thumbnail(thumbnail);
         }
     }
 
+    pub fn get_favorites(&self) -> &Vec<FavoriteCourse> {
+        &self.favorites
+    }
+
+    pub fn set_favorites(&mut self, favorites: Vec<FavoriteCourse>) {
+        self.favorites = favorites;
+    }
+
+    pub fn get_favorite_ids(&self) -> HashSet<String> {
+        self.favorites
+            .iter()
+            .map(|favorite| favorite.id.clone())
+            .collect()
+    }
+
+    /// Adds a course to or removes it from the favorites.
+    pub fn toggle_favorite(&mut self, id: String) {
+        if self.favorites.iter().any(|favorite| favorite.id == id) {
+            self.favorites.retain(|favorite| favorite.id != id);
+            self.favorite_panels.shift_remove(&id);
+            return;
+        }
+        let course = match self
+            .course_responses
+            .get(&id)
+            .or_else(|| self.own_course_responses.get(&id))
+        {
+            Some(course) => course.clone(),
+            None => return,
+        };
+        self.favorites.push(FavoriteCourse {
+            id: id.clone(),
+            title: course.get_course().get_header().get_title().to_string(),
+            uploader: course.get_uploader().clone(),
+        });
+        self.favorite_panels
+            .insert(id, SmmdbCoursePanel::new(course));
+    }
+
+    /// Returns the query for all favorite courses, if there are any.
+    pub fn get_favorites_query(&self) -> Option<QueryParams> {
+        if self.favorites.is_empty() {
+            return None;
+        }
+        let mut query_params = serde_json::from_str::<QueryParams>("{}").unwrap();
+        query_params.ids = Some(self.get_favorite_ids().into_iter().collect());
+        query_params.limit = PROFILE_PAGE_SIZE;
+        Some(query_params)
+    }
+
+    /// Shows the fetched favorite courses in the order in which they have been added and
+    /// returns their ids.
+    pub fn set_favorite_courses(&mut self, courses: Vec<Course2Response>) -> Vec<String> {
+        self.are_favorites_loaded = true;
+        self.set_courses(courses.clone(), false);
+        let mut courses: HashMap<String, Course2Response> = courses
+            .into_iter()
+            .map(|course| (course.get_id().clone(), course))
+            .collect();
+        self.favorite_panels = self
+            .favorites
+            .iter()
+            .filter_map(|favorite| courses.remove(&favorite.id))
+            .map(|course| (course.get_id().clone(), SmmdbCoursePanel::new(course)))
+            .collect();
+        self.favorite_panels.keys().cloned().collect()
+    }
+
+    /// Returns the favorites which could not be fetched, most likely because they have been
+    /// deleted from SMMDB.
+    pub fn get_unavailable_favorites(&self) -> Vec<FavoriteCourse> {
+        if !self.are_favorites_loaded {
+            return vec![];
+        }
+        self.favorites
+            .iter()
+            .filter(|favorite| !self.favorite_panels.contains_key(&favorite.id))
+            .cloned()
+            .collect()
+    }
+
+    pub fn get_favorite_panels_mut(&mut self) -> &mut IndexMap<String, SmmdbCoursePanel> {
+        &mut self.favorite_panels
+    }
+
+    pub fn get_installed_versions(&self) -> &Vec<InstalledVersion> {
+        &self.installed_versions
+    }
+
+    pub fn set_installed_versions(&mut self, installed_versions: Vec<InstalledVersion>) {
+        self.installed_versions = installed_versions;
+    }
+
+    /// Remembers the current version of a course, which has just been downloaded into a slot.
+    ///
+    /// Other copies of the course keep their version, since they have not been updated.
+    pub fn set_installed_version(&mut self, location: &Path, slot: usize, id: &str) {
+        if let Some(course) = self.course_responses.get(id) {
+            self.installed_versions
+                .retain(|version| version.location != location || version.slot != slot);
+            self.installed_versions.push(InstalledVersion {
+                location: location.to_path_buf(),
+                slot,
+                smmdb_id: id.to_string(),
+                last_modified: course.get_last_modified(),
+            });
+        }
+    }
+
+    fn get_installed_version(&self, location: &Path, slot: usize, id: &str) -> Option<i64> {
+        self.installed_versions
+            .iter()
+            .find(|version| {
+                version.location == location && version.slot == slot && version.smmdb_id == id
+            })
+            .map(|version| version.last_modified)
+    }
+
+    /// Returns all installed courses, which have been modified on SMMDB since their installation.
+    ///
+    /// Courses without a known version have been installed before versions were tracked. They
+    /// might be outdated, so they are offered as updates, until they have been downloaded again.
+    pub fn find_course_updates(
+        &self,
+        location: &Path,
+        installed_courses: &HashMap<String, Vec<usize>>,
+        courses: &[Course2Response],
+    ) -> Vec<CourseUpdate> {
+        let mut updates = vec![];
+        for course in courses {
+            let slots = match installed_courses.get(course.get_id()) {
+                Some(slots) => slots,
+                None => continue,
+            };
+            for slot in slots {
+                let installed_version =
+                    self.get_installed_version(location, *slot, course.get_id());
+                if installed_version.map_or(true, |version| course.get_last_modified() > version) {
+                    updates.push(CourseUpdate {
+                        slot: *slot,
+                        smmdb_id: course.get_id().clone(),
+                        title: course.get_course().get_header().get_title().to_string(),
+                        is_version_unknown: installed_version.is_none(),
+                    });
+                }
+            }
+        }
+        updates.sort_by_key(|update| update.slot);
+        updates
+    }
+
+    pub fn get_followed_uploaders(&self) -> &Vec<FollowedUploader> {
+        &self.followed_uploaders
+    }
+
+    pub fn set_followed_uploaders(&mut self, followed_uploaders: Vec<FollowedUploader>) {
+        self.followed_uploaders = followed_uploaders;
+    }
+
+    pub fn is_following(&self, owner: &str) -> bool {
+        self.followed_uploaders
+            .iter()
+            .any(|followed| followed.owner == owner)
+    }
+
+    pub fn follow(&mut self, owner: String, uploader: String) {
+        if !self.is_following(&owner) {
+            self.followed_uploaders.push(FollowedUploader {
+                owner,
+                uploader,
+                last_seen: None,
+            });
+        }
+    }
+
+    pub fn unfollow(&mut self, owner: &str) {
+        self.followed_uploaders
+            .retain(|followed| followed.owner != owner);
+        self.inbox
+            .retain(|_, panel| panel.get_course().get_owner() != owner);
+    }
+
+    /// Adds the courses of a followed uploader which are newer than the last seen course to the
+    /// inbox and returns their ids.
+    ///
+    /// On the first check after following, all courses are considered as seen.
+    pub fn add_new_courses(&mut self, owner: &str, courses: Vec<Course2Response>) -> Vec<String> {
+        let followed = match self
+            .followed_uploaders
+            .iter_mut()
+            .find(|followed| followed.owner == owner)
+        {
+            Some(followed) => followed,
+            None => return vec![],
+        };
+        let last_seen = match followed.last_seen {
+            Some(last_seen) => last_seen,
+            None => {
+                followed.last_seen = Some(
+                    courses
+                        .iter()
+                        .map(|course| course.get_uploaded())
+                        .max()
+                        .unwrap_or_default(),
+                );
+                return vec![];
+            }
+        };
+        let new_courses: Vec<Course2Response> = courses
+            .into_iter()
+            .filter(|course| {
+                course.get_uploaded() > last_seen && !self.inbox.contains_key(course.get_id())
+            })
+            .collect();
+        let ids = new_courses
+            .iter()
+            .map(|course| course.get_id().clone())
+            .collect();
+        self.set_courses(new_courses.clone(), false);
+        for course in new_courses {
+            self.inbox
+                .insert(course.get_id().clone(), SmmdbCoursePanel::new(course));
+        }
+        ids
+    }
+
+    pub fn get_inbox_mut(&mut self) -> &mut IndexMap<String, SmmdbCoursePanel> {
+        &mut self.inbox
+    }
+
+    /// Remembers the courses in the inbox as seen and clears it.
+    pub fn mark_inbox_read(&mut self) {
+        for panel in self.inbox.values() {
+            let course = panel.get_course();
+            if let Some(followed) = self
+                .followed_uploaders
+                .iter_mut()
+                .find(|followed| &followed.owner == course.get_owner())
+            {
+                followed.last_seen = followed.last_seen.max(Some(course.get_uploaded()));
+            }
+        }
+        self.inbox.clear();
+    }
+
+    /// Opens the profile of an uploader and returns the query for all of their courses.
+    pub fn open_profile(&mut self, owner: String, uploader: String) -> QueryParams {
+        let mut query_params = serde_json::from_str::<QueryParams>("{}").unwrap();
+        query_params.owner = Some(owner.clone());
+        query_params.limit = PROFILE_PAGE_SIZE;
+        self.profile = Some(UploaderProfile::new(owner, uploader));
+        query_params
+    }
+
+    pub fn get_profile(&self) -> Option<&UploaderProfile> {
+        self.profile.as_ref()
+    }
+
+    pub fn get_profile_mut(&mut self) -> Option<&mut UploaderProfile> {
+        self.profile.as_mut()
+    }
+
+    pub fn close_profile(&mut self) {
+        self.profile = None;
+    }
+
+    /// Shows the courses of the opened profile and returns their ids.
+    pub fn set_profile_courses(
+        &mut self,
+        owner: &str,
+        courses: Vec<Course2Response>,
+    ) -> Option<Vec<String>> {
+        match self.profile.as_ref() {
+            Some(profile) if profile.owner == owner => {}
+            _ => return None,
+        }
+        let ids = courses
+            .iter()
+            .map(|course| course.get_id().clone())
+            .collect();
+        self.set_courses(courses.clone(), false);
+        if let Some(profile) = self.profile.as_mut() {
+            profile.set_courses(courses);
+        }
+        Some(ids)
+    }
+
     pub fn get_course_panels(&mut self) -> &mut IndexMap<String, SmmdbCoursePanel> {
         &mut self.course_panels
     }
@@ -149,12 +566,96 @@ impl Smmdb {
         &self.query_params
     }
 
+    /// Replaces the current query, e.g. with the query restored from the settings.
+    ///
+    /// A page size of 0 from an edited settings file is raised to 1, since pages are computed by
+    /// dividing by it.
+    pub fn set_query_params(&mut self, mut query_params: QueryParams) {
+        query_params.limit = query_params.limit.max(1);
+        self.query_params = query_params;
+        self.reset_pagination();
+    }
+
+    pub fn get_saved_searches(&self) -> &Vec<SavedSearch> {
+        &self.saved_searches
+    }
+
+    pub fn set_saved_searches(&mut self, saved_searches: Vec<SavedSearch>) {
+        self.saved_searches = saved_searches;
+    }
+
+    pub fn get_saved_search_names(&self) -> Vec<String> {
+        self.saved_searches
+            .iter()
+            .map(|search| search.name.clone())
+            .collect()
+    }
+
+    pub fn get_selected_search(&self) -> Option<String> {
+        self.selected_search.clone()
+    }
+
+    pub fn get_saved_search_name(&self) -> &str {
+        &self.saved_search_name
+    }
+
+    pub fn set_saved_search_name(&mut self, saved_search_name: String) {
+        self.saved_search_name = saved_search_name;
+    }
+
+    /// Saves the current query under the entered name, replacing a search with the same name.
+    pub fn save_search(&mut self) -> Result<()> {
+        let name = self.saved_search_name.trim().to_string();
+        if name.is_empty() {
+            anyhow::bail!("Please enter a name for the search");
+        }
+        let mut query_params = self.query_params.clone();
+        query_params.skip = 0;
+        let search = SavedSearch {
+            name: name.clone(),
+            query_params,
+        };
+        if let Some(existing) = self
+            .saved_searches
+            .iter_mut()
+            .find(|search| search.name == name)
+        {
+            *existing = search;
+        } else {
+            self.saved_searches.push(search);
+        }
+        self.selected_search = Some(name);
+        self.saved_search_name.clear();
+        Ok(())
+    }
+
+    pub fn load_saved_search(&mut self, name: String) {
+        if let Some(search) = self
+            .saved_searches
+            .iter()
+            .find(|search| search.name == name)
+        {
+            self.set_query_params(search.query_params.clone());
+            self.selected_search = Some(name);
+        }
+    }
+
+    pub fn delete_saved_search(&mut self) {
+        if let Some(name) = self.selected_search.take() {
+            self.saved_searches.retain(|search| search.name != name);
+        }
+    }
+
     pub fn get_own_query_params(&self) -> &QueryParams {
         &self.own_query_params
     }
 
     pub fn can_paginate_forward(&self) -> bool {
-        self.course_panels.len() as u32 == self.query_params.limit
+        if let Some(total_count) = self.total_count {
+            self.query_params.skip + self.query_params.limit < total_count
+        } else {
+            self.course_panels.len() as u32 == self.query_params.limit
+        }
     }
 
     pub fn can_self_paginate_forward(&self) -> bool {
@@ -187,6 +688,98 @@ impl Smmdb {
 
     pub fn reset_pagination(&mut self) {
         self.query_params.skip = 0;
+        self.total_count = None;
+        self.min_total_count = None;
+        self.query_generation += 1;
+    }
+
+    /// Changes the page size and moves to the page containing the first visible course.
+    pub fn set_page_size(&mut self, limit: u32) {
+        let limit = limit.max(1);
+        self.query_params.skip = self.query_params.skip / limit * limit;
+        self.query_params.limit = limit;
+    }
+
+    pub fn get_page(&self) -> u32 {
+        self.query_params.skip / self.query_params.limit + 1
+    }
+
+    pub fn get_page_count(&self) -> Option<u32> {
+        self.total_count.map(|total_count| {
+            (total_count.saturating_add(self.query_params.limit - 1) / self.query_params.limit)
+                .max(1)
+        })
+    }
+
+    pub fn get_total_count(&self) -> Option<u32> {
+        self.total_count
+    }
+
+    pub fn get_min_total_count(&self) -> Option<u32> {
+        self.min_total_count
+    }
+
+    pub fn get_page_input(&self) -> &str {
+        &self.page_input
+    }
+
+    pub fn set_page_input(&mut self, page_input: String) {
+        self.page_input = page_input;
+    }
+
+    /// Moves to the page entered in the page input.
+    pub fn jump_to_page(&mut self) -> Result<()> {
+        let page: u32 = match self.page_input.trim().parse() {
+            Ok(page) if page > 0 => page,
+            _ => anyhow::bail!("\"{}\" is not a valid page number", self.page_input),
+        };
+        if let Some(page_count) = self.get_page_count() {
+            if page > page_count {
+                anyhow::bail!(
+                    "Page {} does not exist, there are {} pages",
+                    page,
+                    page_count
+                );
+            }
+        }
+        self.query_params.skip = match (page - 1).checked_mul(self.query_params.limit) {
+            Some(skip) => skip,
+            None => anyhow::bail!("Page {} is too large", page),
+        };
+        self.page_input.clear();
+        Ok(())
+    }
+
+    /// Updates the total count after a page has been fetched.
+    ///
+    /// A page which is not full tells the exact count. Otherwise the count is unknown and the
+    /// query and its generation are returned, so that it can be probed with [`Smmdb::count`].
+    /// A query is only probed once, even if counting has been given up.
+    pub fn update_total_count(&mut self) -> Option<(QueryParams, u32, u32)> {
+        let len = self.course_panels.len() as u32;
+        if len < self.query_params.limit && (len > 0 || self.query_params.skip == 0) {
+            self.total_count = Some(self.query_params.skip + len);
+            None
+        } else if self.total_count.is_none() && self.min_total_count.is_none() {
+            let known = if len > 0 {
+                self.query_params.skip + len
+            } else {
+                0
+            };
+            Some((self.query_params.clone(), known, self.query_generation))
+        } else {
+            None
+        }
+    }
+
+    pub fn set_total_count(&mut self, generation: u32, count: CourseCount) {
+        if generation != self.query_generation {
+            return;
+        }
+        match count {
+            CourseCount::Exact(total_count) => self.total_count = Some(total_count),
+            CourseCount::AtLeast(min_total_count) => self.min_total_count = Some(min_total_count),
+        }
     }
 
     pub fn reset_self_pagination(&mut self) {
@@ -209,6 +802,78 @@ impl Smmdb {
         }
     }
 
+    pub fn set_title_exact(&mut self, title_exact: bool) {
+        self.query_params.title_exact = title_exact;
+    }
+
+    pub fn set_title_case_sensitive(&mut self, title_case_sensitive: bool) {
+        self.query_params.title_case_sensitive = title_case_sensitive;
+    }
+
+    pub fn set_title_trimmed(&mut self, title_trimmed: bool) {
+        self.query_params.title_trimmed = title_trimmed;
+    }
+
+    pub fn set_owner(&mut self, owner: String) {
+        if let "" = owner.as_ref() {
+            self.query_params.owner = None;
+        } else {
+            self.query_params.owner = Some(owner);
+        }
+    }
+
+    pub fn set_id(&mut self, id: String) {
+        if let "" = id.as_ref() {
+            self.query_params.id = None;
+        } else {
+            self.query_params.id = Some(id);
+        }
+    }
+
+    /// Sets the course ids from a comma or whitespace separated list.
+    pub fn set_ids(&mut self, ids: String) {
+        let ids: Vec<String> = ids
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|id| !id.is_empty())
+            .map(|id| id.to_string())
+            .collect();
+        if ids.is_empty() {
+            self.query_params.ids = None;
+        } else {
+            self.query_params.ids = Some(ids);
+        }
+    }
+
+    pub fn is_advanced_search_shown(&self) -> bool {
+        self.show_advanced_search
+    }
+
+    pub fn toggle_advanced_search(&mut self) {
+        self.show_advanced_search = !self.show_advanced_search;
+    }
+
+    pub fn get_course_lookup(&self) -> &str {
+        &self.course_lookup
+    }
+
+    pub fn set_course_lookup(&mut self, course_lookup: String) {
+        self.course_lookup = course_lookup;
+    }
+
+    /// Replaces the current query with a query for the course id entered in the lookup field.
+    pub fn apply_course_lookup(&mut self) -> Result<()> {
+        let id = parse_course_id(&self.course_lookup).ok_or_else(|| {
+            anyhow::anyhow!(
+                "\"{}\" is neither a SMMDB course id nor a smmdb.net course URL",
+                self.course_lookup
+            )
+        })?;
+        self.query_params = serde_json::from_str::<QueryParams>("{}").unwrap();
+        self.query_params.id = Some(id);
+        self.show_advanced_search = true;
+        Ok(())
+    }
+
     pub fn set_difficulty(&mut self, difficulty: Difficulty) {
         if let Difficulty::Unset = difficulty {
             self.query_params.difficulty = None;
@@ -217,8 +882,10 @@ impl Smmdb {
         }
     }
 
-    pub fn set_sort(&mut self, sort: SortOptions) {
-        self.query_params.sort = Some(sort);
+    pub fn get_sort_mut(&mut self) -> &mut SortOptions {
+        self.query_params
+            .sort
+            .get_or_insert_with(SortOptions::default)
     }
 
     pub fn set_apikey(&mut self, apikey: String) {
@@ -232,6 +899,19 @@ impl Smmdb {
         if let Some(course) = self.own_course_panels.get_mut(&course_id) {
             course.set_own_vote(value);
         }
+        if let Some(course) = self.inbox.get_mut(&course_id) {
+            course.set_own_vote(value);
+        }
+        if let Some(course) = self.favorite_panels.get_mut(&course_id) {
+            course.set_own_vote(value);
+        }
+        if let Some(course) = self
+            .profile
+            .as_mut()
+            .and_then(|profile| profile.course_panels.get_mut(&course_id))
+        {
+            course.set_own_vote(value);
+        }
         if let Some(course) = self.course_responses.get_mut(&course_id) {
             course.set_own_vote(value)
         }
@@ -253,6 +933,102 @@ impl Smmdb {
         Ok(response)
     }
 
+    /// Counts the courses matching a query, given that at least `known` courses exist.
+    ///
+    /// The API does not report a total, so single courses are probed with an exponential
+    /// search followed by a binary search. After [`MAX_COUNT_PROBES`] requests only a lower bound
+    /// is returned.
+    pub async fn count(
+        query_params: QueryParams,
+        known: u32,
+        apikey: Option<String>,
+    ) -> Result<CourseCount> {
+        let exists = |skip: u32| {
+            let mut query_params = query_params.clone();
+            query_params.skip = skip;
+            query_params.limit = 1;
+            let apikey = apikey.clone();
+            async move {
+                let courses = Smmdb::update(query_params, apikey).await?;
+                Ok::<bool, anyhow::Error>(!courses.is_empty())
+            }
+        };
+
+        // `low` courses are known to exist, the course at `high` is probed.
+        let mut low = known;
+        let mut step = query_params.limit.max(1);
+        let mut high = low.saturating_add(step);
+        let mut probes = 0;
+        loop {
+            probes += 1;
+            if !exists(high).await? {
+                break;
+            }
+            if probes >= MAX_COUNT_PROBES || high == u32::MAX {
+                return Ok(CourseCount::AtLeast(high.saturating_add(1)));
+            }
+            low = high + 1;
+            step = step.saturating_mul(2);
+            high = low.saturating_add(step);
+        }
+        while low < high {
+            if probes >= MAX_COUNT_PROBES {
+                return Ok(CourseCount::AtLeast(low));
+            }
+            probes += 1;
+            let mid = low + (high - low) / 2;
+            if exists(mid).await? {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(CourseCount::Exact(low))
+    }
+
+    /// Fetches the most recently uploaded courses of an uploader.
+    pub async fn fetch_latest_courses(
+        owner: String,
+        apikey: Option<String>,
+    ) -> Result<Vec<Course2Response>> {
+        let mut query_params = serde_json::from_str::<QueryParams>("{}").unwrap();
+        query_params.owner = Some(owner);
+        query_params.sort = Some(SortOptions(vec![Sort::new(
+            SortValue::Uploaded,
+            SortDirection::Descending,
+        )]));
+        Smmdb::update(query_params, apikey).await
+    }
+
+    /// Fetches all pages of a query.
+    ///
+    /// Fetching stops at a page without new courses, in case the API ignores `skip`, and fails
+    /// after [`MAX_FETCHED_PAGES`] pages.
+    pub async fn update_all(
+        mut query_params: QueryParams,
+        apikey: Option<String>,
+    ) -> Result<Vec<Course2Response>> {
+        let mut courses = vec![];
+        let mut ids = HashSet::new();
+        for _ in 0..MAX_FETCHED_PAGES {
+            let page = Smmdb::update(query_params.clone(), apikey.clone()).await?;
+            let is_last_page = (page.len() as u32) < query_params.limit;
+            let len = courses.len();
+            courses.extend(
+                page.into_iter()
+                    .filter(|course| ids.insert(course.get_id().clone())),
+            );
+            if is_last_page || courses.len() == len {
+                return Ok(courses);
+            }
+            query_params.skip += query_params.limit;
+        }
+        anyhow::bail!(
+            "Too many courses, stopped after fetching {} courses",
+            courses.len()
+        )
+    }
+
     pub async fn update_self(
         query_params: QueryParams,
         apikey: Option<String>,
@@ -385,6 +1161,18 @@ impl Course2Response {
         &self.owner
     }
 
+    pub fn get_uploader(&self) -> &String {
+        &self.uploader
+    }
+
+    pub fn get_uploaded(&self) -> i64 {
+        self.uploaded
+    }
+
+    pub fn get_last_modified(&self) -> i64 {
+        self.last_modified
+    }
+
     pub fn get_votes(&self) -> i32 {
         self.votes
     }
@@ -468,6 +1256,132 @@ pub struct QueryParams {
     pub difficulty: Option<Difficulty>,
 }
 
+/// Courses and statistics of a single uploader.
+#[derive(Debug)]
+pub struct UploaderProfile {
+    owner: String,
+    uploader: String,
+    is_loading: bool,
+    course_panels: IndexMap<String, SmmdbCoursePanel>,
+    total_votes: i32,
+    /// Number of courses per difficulty. Courses without difficulty are counted as unset.
+    difficulty_counts: Vec<(Difficulty, usize)>,
+}
+
+impl UploaderProfile {
+    fn new(owner: String, uploader: String) -> UploaderProfile {
+        UploaderProfile {
+            owner,
+            uploader,
+            is_loading: true,
+            course_panels: IndexMap::new(),
+            total_votes: 0,
+            difficulty_counts: vec![],
+        }
+    }
+
+    fn set_courses(&mut self, courses: Vec<Course2Response>) {
+        self.is_loading = false;
+        self.total_votes = courses.iter().map(|course| course.get_votes()).sum();
+        self.difficulty_counts = Difficulty::ALL
+            .iter()
+            .map(|difficulty| {
+                let count = courses
+                    .iter()
+                    .filter(|course| {
+                        course.get_difficulty().unwrap_or(&Difficulty::Unset) == difficulty
+                    })
+                    .count();
+                (*difficulty, count)
+            })
+            .collect();
+        self.course_panels = courses
+            .into_iter()
+            .map(|course| (course.get_id().clone(), SmmdbCoursePanel::new(course)))
+            .collect();
+    }
+
+    pub fn get_owner(&self) -> &String {
+        &self.owner
+    }
+
+    pub fn get_uploader(&self) -> &String {
+        &self.uploader
+    }
+
+    pub fn is_loading(&self) -> bool {
+        self.is_loading
+    }
+
+    pub fn get_total_votes(&self) -> i32 {
+        self.total_votes
+    }
+
+    pub fn get_difficulty_counts(&self) -> &Vec<(Difficulty, usize)> {
+        &self.difficulty_counts
+    }
+
+    pub fn get_course_panels(&mut self) -> &mut IndexMap<String, SmmdbCoursePanel> {
+        &mut self.course_panels
+    }
+}
+
+/// A bookmarked course.
+///
+/// Title and uploader are cached, so that the course can still be listed if it has been deleted
+/// from SMMDB.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FavoriteCourse {
+    pub id: String,
+    pub title: String,
+    pub uploader: String,
+}
+
+/// The SMMDB version of a course, which has been downloaded into a slot of a save.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct InstalledVersion {
+    pub location: PathBuf,
+    pub slot: usize,
+    pub smmdb_id: String,
+    /// SMMDB modification time of the course as of its download.
+    pub last_modified: i64,
+}
+
+/// Result of [`Smmdb::count`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CourseCount {
+    Exact(u32),
+    /// Counting has been given up, but at least this many courses exist.
+    AtLeast(u32),
+}
+
+/// An installed course, which has been modified on SMMDB since it has been downloaded.
+#[derive(Clone, Debug)]
+pub struct CourseUpdate {
+    pub slot: usize,
+    pub smmdb_id: String,
+    pub title: String,
+    /// The course has been installed before versions were tracked, so it might be up to date.
+    pub is_version_unknown: bool,
+}
+
+/// An uploader, whose new courses are shown in the inbox.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FollowedUploader {
+    pub owner: String,
+    pub uploader: String,
+    /// Upload time of the newest course which has been seen.
+    #[serde(default)]
+    pub last_seen: Option<i64>,
+}
+
+/// A named query, which can be recalled in the course browser.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SavedSearch {
+    pub name: String,
+    pub query_params: QueryParams,
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct VoteBody {
     #[serde(default)]
@@ -483,6 +1397,30 @@ impl QueryParams {
         }
     }
 
+    pub fn get_owner(&self) -> &str {
+        if let Some(owner) = self.owner.as_ref() {
+            owner
+        } else {
+            ""
+        }
+    }
+
+    pub fn get_id(&self) -> &str {
+        if let Some(id) = self.id.as_ref() {
+            id
+        } else {
+            ""
+        }
+    }
+
+    pub fn get_ids(&self) -> String {
+        if let Some(ids) = self.ids.as_ref() {
+            ids.join(", ")
+        } else {
+            String::new()
+        }
+    }
+
     pub fn get_uploader(&self) -> &str {
         if let Some(uploader) = self.uploader.as_ref() {
             uploader
@@ -500,6 +1438,43 @@ impl QueryParams {
     }
 }
 
+/// Extracts a course id from either a plain id or a smmdb.net URL, e.g.
+/// `https://smmdb.net/courses2/<id>` or `https://smmdb.net/courses2?id=<id>`.
+pub fn parse_course_id(input: &str) -> Option<String> {
+    let input = input.trim();
+    let is_id = |id: &str| id.len() == 24 && id.chars().all(|c| c.is_ascii_hexdigit());
+    if is_id(input) {
+        return Some(input.to_string());
+    }
+    if !input.contains("smmdb.net") {
+        return None;
+    }
+    let (path, query) = match input.find('?') {
+        Some(index) => (&input[..index], &input[index + 1..]),
+        None => (input, ""),
+    };
+    query
+        .split(|c: char| c == '&' || c == '#')
+        .filter_map(|param| param.strip_prefix("id="))
+        .chain(path.split(|c: char| c == '/' || c == '#').rev())
+        .find(|id| is_id(id))
+        .map(|id| id.to_string())
+}
+
+pub const PAGE_SIZES: [u32; 4] = [10, 25, 50, 100];
+
+/// Page size used to fetch all courses of an uploader.
+const PROFILE_PAGE_SIZE: u32 = 100;
+
+/// Number of pages kept in memory while scrolling infinitely.
+const MAX_LOADED_PAGES: u32 = 4;
+
+/// Maximum number of pages fetched by [`Smmdb::update_all`].
+const MAX_FETCHED_PAGES: u32 = 50;
+
+/// Maximum number of requests sent to count the courses of a query.
+const MAX_COUNT_PROBES: u32 = 10;
+
 fn limit_default() -> u32 {
     25
 }
@@ -511,54 +1486,90 @@ fn is_true() -> bool {
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct Sort {
     pub val: SortValue,
-    dir: i32,
+    /// `1` for ascending and `-1` for descending order.
+    pub dir: i32,
 }
 
-impl Default for Sort {
-    fn default() -> Self {
+impl Sort {
+    pub fn new(val: SortValue, direction: SortDirection) -> Sort {
         Sort {
-            val: SortValue::LastModified,
-            dir: -1,
+            val,
+            dir: match direction {
+                SortDirection::Ascending => 1,
+                SortDirection::Descending => -1,
+            },
+        }
+    }
+
+    pub fn get_direction(&self) -> SortDirection {
+        if self.dir < 0 {
+            SortDirection::Descending
+        } else {
+            SortDirection::Ascending
         }
     }
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, Serialize)]
+impl Default for Sort {
+    fn default() -> Self {
+        Sort::new(SortValue::LastModified, SortDirection::Descending)
+    }
+}
+
+/// Sort keys in order of precedence. Further keys are used if the previous keys are equal.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct SortOptions(Vec<Sort>);
 
-lazy_static! {
-    pub static ref SORT_OPTIONS: [SortOptions; 2] = [
-        SortOptions(vec![Sort {
-            val: SortValue::LastModified,
-            dir: -1,
-        }]),
-        SortOptions(vec![
-            Sort {
-                val: SortValue::Votes,
-                dir: -1,
-            },
-            Sort {
-                val: SortValue::LastModified,
-                dir: -1,
-            },
-        ]),
-    ];
-}
+impl SortOptions {
+    pub fn get_sorts(&self) -> &Vec<Sort> {
+        &self.0
+    }
 
-impl fmt::Display for SortOptions {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.0.get(0).unwrap().val {
-            SortValue::LastModified => write!(f, "Last Modified"),
-            SortValue::Uploaded => write!(f, "Uploaded"),
-            SortValue::CourseHeaderTitle => write!(f, "Title"),
-            SortValue::Votes => write!(f, "Votes"),
+    pub fn set_value(&mut self, index: usize, val: SortValue) {
+        if let Some(sort) = self.0.get_mut(index) {
+            sort.val = val;
+        }
+    }
+
+    pub fn set_direction(&mut self, index: usize, direction: SortDirection) {
+        if let Some(sort) = self.0.get_mut(index) {
+            *sort = Sort::new(sort.val.clone(), direction);
+        }
+    }
+
+    /// Adds a secondary sort key, which is not yet used.
+    pub fn add_key(&mut self) {
+        if let Some(val) = SortValue::ALL
+            .iter()
+            .find(|val| !self.0.iter().any(|sort| &sort.val == *val))
+        {
+            self.0
+                .push(Sort::new(val.clone(), SortDirection::Descending));
+        }
+    }
+
+    /// Removes a sort key. The last remaining key cannot be removed.
+    pub fn remove_key(&mut self, index: usize) {
+        if self.0.len() > 1 && index < self.0.len() {
+            self.0.remove(index);
         }
     }
 }
 
-impl PartialEq for SortOptions {
-    fn eq(&self, other: &Self) -> bool {
-        self.0.eq(&other.0)
+impl Default for SortOptions {
+    fn default() -> Self {
+        SortOptions(vec![Sort::default()])
+    }
+}
+
+impl fmt::Display for SortOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sorts: Vec<String> = self
+            .0
+            .iter()
+            .map(|sort| format!("{} {}", sort.val, sort.get_direction()))
+            .collect();
+        write!(f, "{}", sorts.join(", "))
     }
 }
 
@@ -573,3 +1584,42 @@ pub enum SortValue {
     #[serde(rename = "votes")]
     Votes,
 }
+
+impl SortValue {
+    pub const ALL: [SortValue; 4] = [
+        SortValue::LastModified,
+        SortValue::Uploaded,
+        SortValue::CourseHeaderTitle,
+        SortValue::Votes,
+    ];
+}
+
+impl fmt::Display for SortValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortValue::LastModified => write!(f, "Last Modified"),
+            SortValue::Uploaded => write!(f, "Uploaded"),
+            SortValue::CourseHeaderTitle => write!(f, "Title"),
+            SortValue::Votes => write!(f, "Votes"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SortDirection {
+    Ascending,
+    Descending,
+}
+
+impl SortDirection {
+    pub const ALL: [SortDirection; 2] = [SortDirection::Ascending, SortDirection::Descending];
+}
+
+impl fmt::Display for SortDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SortDirection::Ascending => write!(f, "↑"),
+            SortDirection::Descending => write!(f, "↓"),
+        }
+    }
+}
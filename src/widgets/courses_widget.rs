@@ -1,24 +1,51 @@
+use super::{ScrollSentinel, ScrollSentinelState};
 use crate::{
-    font,
-    smmdb::{Difficulty, SortOptions, SORT_OPTIONS},
+    font, icon,
+    smmdb::{Difficulty, SortDirection, SortValue, PAGE_SIZES},
     styles::*,
     AppState, Message, Smmdb,
 };
 
 use iced::{
-    button, pick_list, text_input, Align, Button, Column, Element, Length, PickList, Row, Space,
-    Text, TextInput,
+    button, pick_list, text_input, Align, Button, Checkbox, Column, Element, Length, PickList, Row,
+    Space, Text, TextInput,
 };
+use std::collections::HashMap;
+
+/// Distance in pixels below the visible area at which the next page is prefetched.
+const PREFETCH_DISTANCE: f32 = 1000.0;
 
 #[derive(Clone, Debug)]
 pub struct CoursesWidget {
     title_state: text_input::State,
     uploader_state: text_input::State,
     difficulty_state: pick_list::State<Difficulty>,
-    sort_state: pick_list::State<SortOptions>,
+    saved_search_state: pick_list::State<String>,
+    delete_search_state: button::State,
+    search_name_state: text_input::State,
+    save_search_state: button::State,
+    advanced_state: button::State,
+    owner_state: text_input::State,
+    id_state: text_input::State,
+    ids_state: text_input::State,
+    lookup_state: text_input::State,
+    lookup_button_state: button::State,
+    sort_states: Vec<SortKeyState>,
+    add_sort_key_state: button::State,
     search_state: button::State,
+    page_size_state: pick_list::State<u32>,
+    page_state: text_input::State,
+    jump_state: button::State,
     backward_state: button::State,
     forward_state: button::State,
+    sentinel_state: ScrollSentinelState,
+}
+
+#[derive(Clone, Debug, Default)]
+struct SortKeyState {
+    value_state: pick_list::State<SortValue>,
+    direction_state: pick_list::State<SortDirection>,
+    remove_state: button::State,
 }
 
 impl CoursesWidget {
@@ -27,10 +54,25 @@ impl CoursesWidget {
             title_state: text_input::State::new(),
             uploader_state: text_input::State::new(),
             difficulty_state: pick_list::State::default(),
-            sort_state: pick_list::State::default(),
+            saved_search_state: pick_list::State::default(),
+            delete_search_state: button::State::new(),
+            search_name_state: text_input::State::new(),
+            save_search_state: button::State::new(),
+            advanced_state: button::State::new(),
+            owner_state: text_input::State::new(),
+            id_state: text_input::State::new(),
+            ids_state: text_input::State::new(),
+            lookup_state: text_input::State::new(),
+            lookup_button_state: button::State::new(),
+            sort_states: vec![],
+            add_sort_key_state: button::State::new(),
             search_state: button::State::new(),
+            page_size_state: pick_list::State::default(),
+            page_state: text_input::State::new(),
+            jump_state: button::State::new(),
             backward_state: button::State::new(),
             forward_state: button::State::new(),
+            sentinel_state: ScrollSentinelState::new(),
         }
     }
 
@@ -38,6 +80,7 @@ impl CoursesWidget {
         &'a mut self,
         state: &AppState,
         smmdb: &'a mut Smmdb,
+        installed_courses: &HashMap<String, Vec<usize>>,
     ) -> impl Into<Element<crate::Message>> {
         let query_params = smmdb.get_query_params();
 
@@ -65,19 +108,219 @@ impl CoursesWidget {
         )
         .style(DefaultPickListStyle)
         .padding(4);
-        let sort_pick_list = PickList::new(
-            &mut self.sort_state,
-            &SORT_OPTIONS[..],
-            query_params.get_sort(),
-            Message::SortChanged,
+        let selected_search = smmdb.get_selected_search();
+        let mut delete_search_button = Button::new(
+            &mut self.delete_search_state,
+            icon::DELETE
+                .clone()
+                .width(Length::Units(20))
+                .height(Length::Units(20)),
         )
-        .style(DefaultPickListStyle)
-        .padding(4);
+        .style(DeleteButtonStyle);
+        if selected_search.is_some() {
+            delete_search_button = delete_search_button.on_press(Message::DeleteSavedSearch);
+        }
+        let saved_searches = Row::new()
+            .spacing(4)
+            .align_items(Align::Center)
+            .push(
+                PickList::new(
+                    &mut self.saved_search_state,
+                    smmdb.get_saved_search_names(),
+                    selected_search,
+                    Message::SavedSearchSelected,
+                )
+                .style(DefaultPickListStyle)
+                .padding(4)
+                .width(Length::Fill),
+            )
+            .push(delete_search_button);
+        let save_search = Row::new()
+            .spacing(4)
+            .align_items(Align::Center)
+            .push(
+                TextInput::new(
+                    &mut self.search_name_state,
+                    "Name of search",
+                    smmdb.get_saved_search_name(),
+                    Message::SavedSearchNameChanged,
+                )
+                .style(DefaultTextInputStyle)
+                .padding(4)
+                .on_submit(Message::SaveSearch),
+            )
+            .push(
+                Button::new(
+                    &mut self.save_search_state,
+                    Text::new("Save search").size(16),
+                )
+                .style(DefaultButtonStyle)
+                .on_press(Message::SaveSearch),
+            );
+
+        let advanced_button = Button::new(
+            &mut self.advanced_state,
+            Text::new(if smmdb.is_advanced_search_shown() {
+                "Hide advanced search"
+            } else {
+                "Advanced search"
+            })
+            .size(16),
+        )
+        .style(DefaultButtonStyle)
+        .on_press(Message::ToggleAdvancedSearch);
+        let advanced_search = if smmdb.is_advanced_search_shown() {
+            Some(
+                Column::new()
+                    .spacing(4)
+                    .push(Checkbox::new(
+                        query_params.title_exact,
+                        "Exact title",
+                        Message::TitleExactChanged,
+                    ))
+                    .push(Checkbox::new(
+                        query_params.title_case_sensitive,
+                        "Case sensitive title",
+                        Message::TitleCaseSensitiveChanged,
+                    ))
+                    .push(Checkbox::new(
+                        query_params.title_trimmed,
+                        "Trim title",
+                        Message::TitleTrimmedChanged,
+                    ))
+                    .push(
+                        TextInput::new(
+                            &mut self.owner_state,
+                            "Owner id",
+                            query_params.get_owner(),
+                            Message::OwnerChanged,
+                        )
+                        .style(DefaultTextInputStyle)
+                        .padding(4),
+                    )
+                    .push(
+                        TextInput::new(
+                            &mut self.id_state,
+                            "Course id",
+                            query_params.get_id(),
+                            Message::CourseIdChanged,
+                        )
+                        .style(DefaultTextInputStyle)
+                        .padding(4),
+                    )
+                    .push(
+                        TextInput::new(
+                            &mut self.ids_state,
+                            "Course ids, separated by commas",
+                            &query_params.get_ids(),
+                            Message::CourseIdsChanged,
+                        )
+                        .style(DefaultTextInputStyle)
+                        .padding(4),
+                    ),
+            )
+        } else {
+            None
+        };
+        let lookup = Row::new()
+            .spacing(4)
+            .align_items(Align::Center)
+            .push(
+                TextInput::new(
+                    &mut self.lookup_state,
+                    "SMMDB course id or smmdb.net URL",
+                    smmdb.get_course_lookup(),
+                    Message::CourseLookupChanged,
+                )
+                .style(DefaultTextInputStyle)
+                .padding(4)
+                .on_submit(Message::LookupCourse),
+            )
+            .push(
+                Button::new(&mut self.lookup_button_state, Text::new("Go").size(16))
+                    .style(DefaultButtonStyle)
+                    .on_press(Message::LookupCourse),
+            );
+
+        let sort = query_params.get_sort().unwrap_or_default();
+        let sorts = sort.get_sorts();
+        self.sort_states.resize_with(sorts.len(), Default::default);
+        let can_remove_sort_key = sorts.len() > 1;
+        let sort_keys = sorts
+            .iter()
+            .zip(self.sort_states.iter_mut())
+            .enumerate()
+            .fold(
+                Column::new().spacing(4),
+                |acc, (index, (sort, sort_state))| {
+                    let mut row = Row::new()
+                        .spacing(4)
+                        .align_items(Align::Center)
+                        .push(
+                            PickList::new(
+                                &mut sort_state.value_state,
+                                &SortValue::ALL[..],
+                                Some(sort.val.clone()),
+                                move |val| Message::SortValueChanged(index, val),
+                            )
+                            .style(DefaultPickListStyle)
+                            .padding(4),
+                        )
+                        .push(
+                            PickList::new(
+                                &mut sort_state.direction_state,
+                                &SortDirection::ALL[..],
+                                Some(sort.get_direction()),
+                                move |direction| Message::SortDirectionChanged(index, direction),
+                            )
+                            .style(DefaultPickListStyle)
+                            .padding(4),
+                        );
+                    if can_remove_sort_key {
+                        row = row.push(
+                            Button::new(
+                                &mut sort_state.remove_state,
+                                icon::DELETE
+                                    .clone()
+                                    .width(Length::Units(20))
+                                    .height(Length::Units(20)),
+                            )
+                            .style(DeleteButtonStyle)
+                            .on_press(Message::RemoveSortKey(index)),
+                        );
+                    }
+                    acc.push(row)
+                },
+            );
+        let mut add_sort_key_button = Button::new(
+            &mut self.add_sort_key_state,
+            Text::new("Add sort key").size(16),
+        )
+        .style(DefaultButtonStyle);
+        if sorts.len() < SortValue::ALL.len() {
+            add_sort_key_button = add_sort_key_button.on_press(Message::AddSortKey);
+        }
         let search_button = Button::new(&mut self.search_state, Text::new("Search"))
             .style(DefaultButtonStyle)
             .on_press(Message::ApplyFilters);
 
-        let filter = Column::new()
+        let mut filter = Column::new()
+            .push(
+                Text::new("Find course:")
+                    .font(font::HELVETICA_BOLD)
+                    .size(16),
+            )
+            .push(lookup)
+            .push(Space::with_height(Length::Units(8)))
+            .push(
+                Text::new("Saved searches:")
+                    .font(font::HELVETICA_BOLD)
+                    .size(16),
+            )
+            .push(saved_searches)
+            .push(Space::with_height(Length::Units(4)))
+            .push(save_search)
+            .push(Space::with_height(Length::Units(8)))
             .push(Text::new("Filters:").font(font::HELVETICA_BOLD).size(16))
             .push(title_text_input)
             .push(Space::with_height(Length::Units(4)))
@@ -85,17 +328,62 @@ impl CoursesWidget {
             .push(Space::with_height(Length::Units(4)))
             .push(difficulty_pick_list)
             .push(Space::with_height(Length::Units(4)))
+            .push(advanced_button);
+        if let Some(advanced_search) = advanced_search {
+            filter = filter
+                .push(Space::with_height(Length::Units(4)))
+                .push(advanced_search);
+        }
+        let filter = filter
             .push(Space::with_height(Length::Units(8)))
             .push(Text::new("Sort by:").font(font::HELVETICA_BOLD).size(16))
-            .push(sort_pick_list)
+            .push(sort_keys)
+            .push(Space::with_height(Length::Units(4)))
+            .push(add_sort_key_button)
             .push(Space::with_height(Length::Units(4)))
             .push(search_button);
 
-        let paginate_text = Text::new(&format!(
-            "{} – {}",
-            query_params.skip + 1,
-            query_params.skip + smmdb.get_course_panels().len() as u32
-        ));
+        let limit = query_params.limit;
+        let first = query_params.skip + 1;
+        let last = query_params.skip + smmdb.get_course_panels().len() as u32;
+        let paginate_text = Text::new(
+            &match (smmdb.get_total_count(), smmdb.get_min_total_count()) {
+                (Some(total_count), _) => format!("{} – {} of {}", first, last, total_count),
+                (None, Some(min_total_count)) => {
+                    format!("{} – {} of {}+", first, last, min_total_count)
+                }
+                (None, None) => format!("{} – {}", first, last),
+            },
+        );
+        let page_text = Text::new(&match smmdb.get_page_count() {
+            Some(page_count) => format!("Page {} of {}", smmdb.get_page(), page_count),
+            None => format!("Page {}", smmdb.get_page()),
+        });
+        let is_loading = matches!(state, AppState::Loading | AppState::Downloading { .. });
+
+        let page_size_pick_list = PickList::new(
+            &mut self.page_size_state,
+            &PAGE_SIZES[..],
+            Some(limit),
+            Message::PageSizeChanged,
+        )
+        .style(DefaultPickListStyle)
+        .padding(4);
+        let mut page_text_input = TextInput::new(
+            &mut self.page_state,
+            "Page",
+            smmdb.get_page_input(),
+            Message::PageInputChanged,
+        )
+        .style(DefaultTextInputStyle)
+        .padding(4)
+        .width(Length::Units(60));
+        let mut jump_button =
+            Button::new(&mut self.jump_state, Text::new("Go").size(16)).style(DefaultButtonStyle);
+        if !is_loading {
+            page_text_input = page_text_input.on_submit(Message::JumpToPage);
+            jump_button = jump_button.on_press(Message::JumpToPage);
+        }
 
         let mut backward_button = Button::new(&mut self.backward_state, Text::new("<").size(24))
             .style(DefaultButtonStyle);
@@ -124,7 +412,21 @@ impl CoursesWidget {
 
         let paginator = Row::new()
             .align_items(Align::Center)
+            .spacing(4)
+            .push(Text::new("Per page:").size(16))
+            .push(page_size_pick_list)
+            .push(Space::with_width(Length::Units(16)))
+            .push(page_text_input)
+            .push(jump_button)
+            .push(Space::with_width(Length::Units(16)))
+            .push(Checkbox::new(
+                smmdb.is_infinite_scroll(),
+                "Infinite scroll",
+                Message::InfiniteScrollChanged,
+            ))
             .push(Space::with_width(Length::Fill))
+            .push(page_text)
+            .push(Space::with_width(Length::Units(16)))
             .push(paginate_text)
             .push(Space::with_width(Length::Units(16)))
             .push(backward_button)
@@ -139,8 +441,26 @@ impl CoursesWidget {
             .push(paginator);
 
         let smmdb_user = smmdb.get_user().cloned();
+        let favorite_ids = smmdb.get_favorite_ids();
+        let show_sentinel = smmdb.is_infinite_scroll() && smmdb.can_load_more();
+        let is_prefetching = smmdb.is_prefetching();
         for panel in smmdb.get_course_panels().values_mut() {
-            content = content.push(panel.view(state, smmdb_user.as_ref()));
+            let is_favorite = favorite_ids.contains(panel.get_id());
+            let installed_slots = installed_courses
+                .get(panel.get_id())
+                .map_or(&[][..], |slots| &slots[..]);
+            content =
+                content.push(panel.view(state, smmdb_user.as_ref(), is_favorite, installed_slots));
+        }
+        if show_sentinel {
+            if is_prefetching {
+                content = content.push(Text::new("Loading more courses…").color(TEXT_HELP_COLOR));
+            }
+            content = content.push(ScrollSentinel::new(
+                &self.sentinel_state,
+                PREFETCH_DISTANCE,
+                Message::PrefetchCourses,
+            ));
         }
 
         content.width(Length::FillPortion(1))
@@ -0,0 +1,79 @@
+use crate::{
+    font,
+    save_diff::{DiffFormat, SaveDiff},
+    styles::*,
+    Message,
+};
+
+use iced::{button, Align, Button, Column, Element, Length, Row, Space, Text};
+
+/// Shows the differences between the two opened saves.
+#[derive(Clone, Debug)]
+pub struct DiffWidget {
+    diff: SaveDiff,
+    export_json_state: button::State,
+    export_markdown_state: button::State,
+    close_state: button::State,
+}
+
+impl DiffWidget {
+    pub fn new(diff: SaveDiff) -> DiffWidget {
+        DiffWidget {
+            diff,
+            export_json_state: button::State::new(),
+            export_markdown_state: button::State::new(),
+            close_state: button::State::new(),
+        }
+    }
+
+    pub fn get_diff(&self) -> &SaveDiff {
+        &self.diff
+    }
+
+    pub fn view(&mut self) -> Element<Message> {
+        let header = Row::new()
+            .align_items(Align::Center)
+            .spacing(LIST_SPACING)
+            .push(Text::new("Comparison:").font(font::HELVETICA_BOLD))
+            .push(Text::new(format!(
+                "{} unchanged, {} changes",
+                self.diff.unchanged,
+                self.diff.entries.len()
+            )))
+            .push(Space::with_width(Length::Fill))
+            .push(
+                Button::new(&mut self.export_json_state, Text::new("Export JSON"))
+                    .padding(BUTTON_PADDING)
+                    .style(DefaultButtonStyle)
+                    .on_press(Message::ExportSaveDiff(DiffFormat::Json)),
+            )
+            .push(
+                Button::new(
+                    &mut self.export_markdown_state,
+                    Text::new("Export Markdown"),
+                )
+                .padding(BUTTON_PADDING)
+                .style(DefaultButtonStyle)
+                .on_press(Message::ExportSaveDiff(DiffFormat::Markdown)),
+            )
+            .push(
+                Button::new(&mut self.close_state, Text::new("Close"))
+                    .padding(BUTTON_PADDING)
+                    .style(DefaultButtonStyle)
+                    .on_press(Message::CloseSaveDiff),
+            );
+
+        let content = Column::new()
+            .padding(CONTAINER_PADDING)
+            .spacing(LIST_SPACING)
+            .push(header);
+        let content = if self.diff.entries.is_empty() {
+            content.push(Text::new("No differences found.").color(TEXT_HELP_COLOR))
+        } else {
+            self.diff.entries.iter().fold(content, |acc, entry| {
+                acc.push(Text::new(entry.to_string()).size(16))
+            })
+        };
+        content.into()
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{styles::*, AppState, Message, Smmdb};
+
+use iced::{button, Align, Button, Column, Element, Length, Row, Text};
+use std::collections::HashMap;
+
+/// Lists the courses which have been bookmarked by the user.
+#[derive(Clone, Debug)]
+pub struct FavoritesWidget {
+    remove_states: Vec<button::State>,
+}
+
+impl FavoritesWidget {
+    pub fn new() -> FavoritesWidget {
+        FavoritesWidget {
+            remove_states: vec![],
+        }
+    }
+
+    pub fn view<'a>(
+        &'a mut self,
+        state: &AppState,
+        smmdb: &'a mut Smmdb,
+        installed_courses: &HashMap<String, Vec<usize>>,
+    ) -> impl Into<Element<Message>> {
+        let smmdb_user = smmdb.get_user().cloned();
+        let favorite_ids = smmdb.get_favorite_ids();
+        let mut content = Column::new()
+            .padding(TAB_PADDING)
+            .spacing(LIST_SPACING)
+            .width(Length::FillPortion(1));
+
+        if smmdb.get_favorites().is_empty() {
+            return content.push(
+                Text::new("You have no favorites yet. Favorite courses to find them here later.")
+                    .color(TEXT_HELP_COLOR),
+            );
+        }
+
+        let unavailable = smmdb.get_unavailable_favorites();
+        self.remove_states
+            .resize_with(unavailable.len(), Default::default);
+
+        for panel in smmdb.get_favorite_panels_mut().values_mut() {
+            let is_favorite = favorite_ids.contains(panel.get_id());
+            let installed_slots = installed_courses
+                .get(panel.get_id())
+                .map_or(&[][..], |slots| &slots[..]);
+            content =
+                content.push(panel.view(state, smmdb_user.as_ref(), is_favorite, installed_slots));
+        }
+        for (favorite, remove_state) in unavailable.into_iter().zip(self.remove_states.iter_mut()) {
+            content = content.push(
+                Row::new()
+                    .spacing(LIST_SPACING)
+                    .align_items(Align::Center)
+                    .push(
+                        Text::new(format!(
+                            "\"{}\" by {} is no longer available",
+                            favorite.title, favorite.uploader
+                        ))
+                        .color(TEXT_HELP_COLOR),
+                    )
+                    .push(
+                        Button::new(remove_state, Text::new("Remove").size(14))
+                            .style(DefaultButtonStyle)
+                            .on_press(Message::ToggleFavorite(favorite.id)),
+                    ),
+            );
+        }
+        content
+    }
+}
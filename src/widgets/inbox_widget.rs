@@ -0,0 +1,111 @@
+use crate::{font, styles::*, AppState, Message, Smmdb};
+
+use iced::{button, Align, Button, Column, Element, Length, Row, Space, Text};
+use std::collections::HashMap;
+
+/// Shows new courses of followed uploaders.
+#[derive(Clone, Debug)]
+pub struct InboxWidget {
+    check_state: button::State,
+    mark_read_state: button::State,
+    unfollow_states: Vec<button::State>,
+}
+
+impl InboxWidget {
+    pub fn new() -> InboxWidget {
+        InboxWidget {
+            check_state: button::State::new(),
+            mark_read_state: button::State::new(),
+            unfollow_states: vec![],
+        }
+    }
+
+    pub fn view<'a>(
+        &'a mut self,
+        state: &AppState,
+        smmdb: &'a mut Smmdb,
+        installed_courses: &HashMap<String, Vec<usize>>,
+    ) -> impl Into<Element<Message>> {
+        let smmdb_user = smmdb.get_user().cloned();
+        let favorite_ids = smmdb.get_favorite_ids();
+        let followed_uploaders = smmdb.get_followed_uploaders().clone();
+
+        let mut mark_read_button = Button::new(
+            &mut self.mark_read_state,
+            Text::new("Mark all as read").size(16),
+        )
+        .style(DefaultButtonStyle);
+        if !smmdb.get_inbox_mut().is_empty() {
+            mark_read_button = mark_read_button.on_press(Message::MarkInboxRead);
+        }
+        let mut check_button = Button::new(&mut self.check_state, Text::new("Check now").size(16))
+            .style(DefaultButtonStyle);
+        if !followed_uploaders.is_empty() {
+            check_button = check_button.on_press(Message::CheckFollowedUploaders);
+        }
+
+        self.unfollow_states
+            .resize_with(followed_uploaders.len(), Default::default);
+        let has_followed_uploaders = !followed_uploaders.is_empty();
+        let mut followed = followed_uploaders
+            .into_iter()
+            .zip(self.unfollow_states.iter_mut())
+            .fold(
+                Column::new().spacing(4).push(
+                    Text::new("Followed uploaders:")
+                        .font(font::HELVETICA_BOLD)
+                        .size(16),
+                ),
+                |acc, (followed, unfollow_state)| {
+                    acc.push(
+                        Row::new()
+                            .spacing(LIST_SPACING)
+                            .align_items(Align::Center)
+                            .push(Text::new(followed.uploader).size(16))
+                            .push(
+                                Button::new(unfollow_state, Text::new("Unfollow").size(14))
+                                    .style(DefaultButtonStyle)
+                                    .on_press(Message::UnfollowUploader(followed.owner)),
+                            ),
+                    )
+                },
+            );
+        if !has_followed_uploaders {
+            followed = followed.push(
+                Text::new("Open the profile of an uploader to follow them.")
+                    .size(16)
+                    .color(TEXT_HELP_COLOR),
+            );
+        }
+
+        let mut content = Column::new()
+            .padding(TAB_PADDING)
+            .spacing(LIST_SPACING)
+            .width(Length::FillPortion(1))
+            .push(
+                Row::new()
+                    .spacing(LIST_SPACING)
+                    .align_items(Align::Center)
+                    .push(Text::new("New courses:").font(font::HELVETICA_BOLD))
+                    .push(Space::with_width(Length::Fill))
+                    .push(check_button)
+                    .push(mark_read_button),
+            );
+
+        let inbox = smmdb.get_inbox_mut();
+        if inbox.is_empty() {
+            content = content.push(
+                Text::new("There are no new courses of followed uploaders.").color(TEXT_HELP_COLOR),
+            );
+        }
+        for panel in inbox.values_mut() {
+            let is_favorite = favorite_ids.contains(panel.get_id());
+            let installed_slots = installed_courses
+                .get(panel.get_id())
+                .map_or(&[][..], |slots| &slots[..]);
+            content =
+                content.push(panel.view(state, smmdb_user.as_ref(), is_favorite, installed_slots));
+        }
+        content.push(followed)
+    }
+}
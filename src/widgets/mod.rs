@@ -1,9 +1,23 @@
 mod courses_widget;
+mod diff_widget;
+mod favorites_widget;
+mod inbox_widget;
+mod profile_widget;
 mod save_widget;
+mod scroll_sentinel;
 mod smmdb_widget;
+mod transfer_widget;
+mod updates_widget;
 mod uploads_widget;
 
 pub use courses_widget::*;
+pub use diff_widget::*;
+pub use favorites_widget::*;
+pub use inbox_widget::*;
+pub use profile_widget::*;
 pub use save_widget::*;
+pub use scroll_sentinel::*;
 pub use smmdb_widget::*;
+pub use transfer_widget::*;
+pub use updates_widget::*;
 pub use uploads_widget::*;
@@ -0,0 +1,104 @@
+use crate::{font, smmdb::Difficulty, styles::*, AppState, Message, Smmdb};
+
+use iced::{button, Align, Button, Column, Element, Length, Row, Space, Text};
+use std::collections::HashMap;
+
+/// Lists all courses of an uploader together with some statistics.
+#[derive(Clone, Debug)]
+pub struct ProfileWidget {
+    follow_state: button::State,
+    close_state: button::State,
+}
+
+impl ProfileWidget {
+    pub fn new() -> ProfileWidget {
+        ProfileWidget {
+            follow_state: button::State::new(),
+            close_state: button::State::new(),
+        }
+    }
+
+    pub fn view<'a>(
+        &'a mut self,
+        state: &AppState,
+        smmdb: &'a mut Smmdb,
+        installed_courses: &HashMap<String, Vec<usize>>,
+    ) -> impl Into<Element<Message>> {
+        let smmdb_user = smmdb.get_user().cloned();
+        let favorite_ids = smmdb.get_favorite_ids();
+        let is_following = smmdb
+            .get_profile()
+            .map_or(false, |profile| smmdb.is_following(profile.get_owner()));
+        let mut content = Column::new()
+            .padding(TAB_PADDING)
+            .spacing(LIST_SPACING)
+            .width(Length::FillPortion(1));
+
+        let profile = match smmdb.get_profile_mut() {
+            Some(profile) => profile,
+            None => return content,
+        };
+
+        content = content.push(
+            Row::new()
+                .align_items(Align::Center)
+                .push(
+                    Text::new(profile.get_uploader())
+                        .font(font::HELVETICA_BOLD)
+                        .size(24),
+                )
+                .push(Space::with_width(Length::Fill))
+                .push(
+                    Button::new(
+                        &mut self.follow_state,
+                        Text::new(if is_following { "Unfollow" } else { "Follow" }).size(16),
+                    )
+                    .style(DefaultButtonStyle)
+                    .on_press(if is_following {
+                        Message::UnfollowUploader(profile.get_owner().clone())
+                    } else {
+                        Message::FollowUploader(
+                            profile.get_owner().clone(),
+                            profile.get_uploader().clone(),
+                        )
+                    }),
+                )
+                .push(
+                    Button::new(&mut self.close_state, Text::new("Close profile").size(16))
+                        .style(DefaultButtonStyle)
+                        .on_press(Message::CloseUploaderProfile),
+                ),
+        );
+        if profile.is_loading() {
+            return content.push(Text::new("Loading courses…").color(TEXT_HELP_COLOR));
+        }
+
+        let difficulties = profile
+            .get_difficulty_counts()
+            .iter()
+            .filter(|(_, count)| *count > 0)
+            .map(|(difficulty, count)| match difficulty {
+                Difficulty::Unset => format!("{} without difficulty", count),
+                _ => format!("{} {}", count, difficulty),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        content = content
+            .push(Text::new(format!(
+                "{} courses, {} votes in total",
+                profile.get_course_panels().len(),
+                profile.get_total_votes()
+            )))
+            .push(Text::new(difficulties).size(16).color(TEXT_HELP_COLOR));
+
+        for panel in profile.get_course_panels().values_mut() {
+            let is_favorite = favorite_ids.contains(panel.get_id());
+            let installed_slots = installed_courses
+                .get(panel.get_id())
+                .map_or(&[][..], |slots| &slots[..]);
+            content =
+                content.push(panel.view(state, smmdb_user.as_ref(), is_favorite, installed_slots));
+        }
+        content
+    }
+}
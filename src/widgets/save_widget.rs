@@ -1,12 +1,16 @@
 use crate::{
     components::CoursePanel,
     font,
-    smmdb::{Course2Response, SmmdbUser},
+    save_filter::{CourseTheme, GameStyle, SaveFilter, SlotFilter, UploadedFilter},
+    smmdb::{Course2Response, Difficulty, SmmdbUser},
     styles::*,
-    AppState,
+    AppState, Message,
 };
 
-use iced::{scrollable, Element, Length, Scrollable, Text};
+use iced::{
+    button, pick_list, scrollable, text_input, Align, Button, Column, Element, Length, PickList,
+    Row, Scrollable, Space, Text, TextInput,
+};
 use smmdb_lib::CourseEntry;
 use std::collections::HashMap;
 
@@ -14,6 +18,17 @@ use std::collections::HashMap;
 pub struct SaveWidget {
     state: scrollable::State,
     course_panels: Vec<CoursePanel>,
+    filter: SaveFilter,
+    filter_text_state: text_input::State,
+    game_style_state: pick_list::State<GameStyle>,
+    theme_state: pick_list::State<CourseTheme>,
+    uploaded_state: pick_list::State<UploadedFilter>,
+    difficulty_state: pick_list::State<Difficulty>,
+    slot_state: pick_list::State<SlotFilter>,
+    reset_filter_state: button::State,
+    filter_enabled: bool,
+    /// Only this slot is shown, e.g. after jumping to an installed SMMDB course.
+    focused_slot: Option<usize>,
 }
 
 impl SaveWidget {
@@ -25,9 +40,43 @@ impl SaveWidget {
         SaveWidget {
             state: scrollable::State::new(),
             course_panels,
+            filter: SaveFilter::default(),
+            filter_text_state: text_input::State::new(),
+            game_style_state: pick_list::State::default(),
+            theme_state: pick_list::State::default(),
+            uploaded_state: pick_list::State::default(),
+            difficulty_state: pick_list::State::default(),
+            slot_state: pick_list::State::default(),
+            reset_filter_state: button::State::new(),
+            filter_enabled: true,
+            focused_slot: None,
         }
     }
 
+    pub fn get_course_panel_mut(&mut self, index: usize) -> Option<&mut CoursePanel> {
+        self.course_panels.get_mut(index)
+    }
+
+    pub fn get_filter_mut(&mut self) -> &mut SaveFilter {
+        &mut self.filter
+    }
+
+    pub fn reset_filter(&mut self) {
+        self.filter = SaveFilter::default();
+        self.focused_slot = None;
+    }
+
+    pub fn focus_slot(&mut self, index: usize) {
+        self.focused_slot = Some(index);
+    }
+
+    /// Hides the filter bar, e.g. for the second save of the split view, because the filter
+    /// messages always apply to the main save.
+    pub fn disable_filter(&mut self) {
+        self.filter_enabled = false;
+        self.filter = SaveFilter::default();
+    }
+
     pub fn set_course_response(&mut self, courses: &HashMap<String, Course2Response>) {
         self.course_panels
             .iter_mut()
@@ -51,13 +100,129 @@ impl SaveWidget {
         state: &AppState,
         display_name: &str,
         smmdb_user: Option<&SmmdbUser>,
+        read_only: bool,
     ) -> Element<crate::Message> {
+        let filter = &self.filter;
+        let focused_slot = self.focused_slot;
+        let is_visible = |index: usize, panel: &CoursePanel| {
+            focused_slot.map_or(true, |slot| slot == index) && panel.matches(filter)
+        };
+        let visible_count = self
+            .course_panels
+            .iter()
+            .enumerate()
+            .filter(|(index, panel)| is_visible(*index, *panel))
+            .count();
+
+        let filter_text_input = TextInput::new(
+            &mut self.filter_text_state,
+            "Title or description",
+            &filter.text,
+            Message::SaveFilterTextChanged,
+        )
+        .style(DefaultTextInputStyle)
+        .padding(4);
+        let game_style_pick_list = PickList::new(
+            &mut self.game_style_state,
+            &GameStyle::ALL[..],
+            filter.game_style,
+            Message::SaveFilterGameStyleChanged,
+        )
+        .style(DefaultPickListStyle)
+        .padding(4);
+        let theme_pick_list = PickList::new(
+            &mut self.theme_state,
+            &CourseTheme::ALL[..],
+            filter.theme,
+            Message::SaveFilterThemeChanged,
+        )
+        .style(DefaultPickListStyle)
+        .padding(4);
+        let uploaded_pick_list = PickList::new(
+            &mut self.uploaded_state,
+            &UploadedFilter::ALL[..],
+            filter.uploaded,
+            Message::SaveFilterUploadedChanged,
+        )
+        .style(DefaultPickListStyle)
+        .padding(4);
+        let difficulty_pick_list = PickList::new(
+            &mut self.difficulty_state,
+            &Difficulty::ALL[..],
+            filter.difficulty,
+            Message::SaveFilterDifficultyChanged,
+        )
+        .style(DefaultPickListStyle)
+        .padding(4);
+        let slot_pick_list = PickList::new(
+            &mut self.slot_state,
+            &SlotFilter::ALL[..],
+            filter.slot,
+            Message::SaveFilterSlotChanged,
+        )
+        .style(DefaultPickListStyle)
+        .padding(4);
+        let mut reset_filter_button =
+            Button::new(&mut self.reset_filter_state, Text::new("Reset")).style(DefaultButtonStyle);
+        if filter.is_active() || focused_slot.is_some() {
+            reset_filter_button = reset_filter_button.on_press(Message::ResetSaveFilter);
+        }
+
+        let filter_content = Column::new()
+            .push(Text::new("Filters:").font(font::HELVETICA_BOLD).size(16))
+            .push(filter_text_input)
+            .push(Space::with_height(Length::Units(4)))
+            .push(
+                Row::new()
+                    .spacing(4)
+                    .push(game_style_pick_list)
+                    .push(theme_pick_list)
+                    .push(difficulty_pick_list),
+            )
+            .push(Space::with_height(Length::Units(4)))
+            .push(
+                Row::new()
+                    .spacing(4)
+                    .align_items(Align::Center)
+                    .push(uploaded_pick_list)
+                    .push(slot_pick_list)
+                    .push(Space::with_width(Length::Fill))
+                    .push(Text::new(format!(
+                        "{} / {}",
+                        visible_count,
+                        self.course_panels.len()
+                    )))
+                    .push(reset_filter_button),
+            );
+        let filter_content = if let Some(slot) = focused_slot {
+            filter_content.push(
+                Text::new(format!("Only slot {} is shown.", slot + 1))
+                    .size(16)
+                    .color(TEXT_HELP_COLOR),
+            )
+        } else {
+            filter_content
+        };
+
         let mut content = Scrollable::new(&mut self.state)
             .padding(CONTAINER_PADDING)
             .spacing(LIST_SPACING)
             .push(Text::new(display_name).font(font::SMME));
+        if read_only {
+            content = content.push(
+                Text::new("Read-only: changes to this save are disabled.")
+                    .size(16)
+                    .color(TEXT_HELP_COLOR),
+            );
+        }
+        if self.filter_enabled {
+            content = content.push(filter_content);
+        }
         for (index, panel) in self.course_panels.iter_mut().enumerate() {
-            content = content.push(panel.view(state, index, smmdb_user));
+            if !is_visible(index, panel) {
+                continue;
+            }
+            content = content.push(panel.view(state, index, smmdb_user, read_only));
         }
 
         content.width(Length::FillPortion(1)).into()
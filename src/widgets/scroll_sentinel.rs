@@ -0,0 +1,102 @@
+use iced_native::{
+    event, layout, widget::space, Clipboard, Element, Event, Hasher, Layout, Length, Point,
+    Rectangle, Size, Widget,
+};
+use std::{cell::Cell, hash::Hash};
+
+/// Remembers whether a [`ScrollSentinel`] was drawn close to the visible area.
+#[derive(Clone, Debug, Default)]
+pub struct ScrollSentinelState {
+    is_near: Cell<bool>,
+}
+
+impl ScrollSentinelState {
+    pub fn new() -> ScrollSentinelState {
+        ScrollSentinelState::default()
+    }
+}
+
+/// Invisible widget placed at the end of a scrollable list.
+///
+/// Scrollables do not report their scroll position, so the sentinel checks in which part of the
+/// content the scrollable draws it and publishes a message on the next event once it is within
+/// `distance` pixels of the visible area.
+pub struct ScrollSentinel<'a, Message> {
+    state: &'a ScrollSentinelState,
+    distance: f32,
+    on_near: Message,
+}
+
+impl<'a, Message> ScrollSentinel<'a, Message> {
+    pub fn new(state: &'a ScrollSentinelState, distance: f32, on_near: Message) -> Self {
+        ScrollSentinel {
+            state,
+            distance,
+            on_near,
+        }
+    }
+}
+
+impl<'a, Message, Renderer> Widget<Message, Renderer> for ScrollSentinel<'a, Message>
+where
+    Message: Clone,
+    Renderer: space::Renderer,
+{
+    fn width(&self) -> Length {
+        Length::Fill
+    }
+
+    fn height(&self) -> Length {
+        Length::Shrink
+    }
+
+    fn layout(&self, _renderer: &Renderer, limits: &layout::Limits) -> layout::Node {
+        let limits = limits.width(Length::Fill).height(Length::Units(1));
+
+        layout::Node::new(limits.resolve(Size::ZERO))
+    }
+
+    fn draw(
+        &self,
+        renderer: &mut Renderer,
+        _defaults: &Renderer::Defaults,
+        layout: Layout<'_>,
+        _cursor_position: Point,
+        viewport: &Rectangle,
+    ) -> Renderer::Output {
+        let bounds = layout.bounds();
+        self.state
+            .is_near
+            .set(bounds.y - (viewport.y + viewport.height) < self.distance);
+        renderer.draw(bounds)
+    }
+
+    fn hash_layout(&self, state: &mut Hasher) {
+        std::any::TypeId::of::<ScrollSentinelState>().hash(state);
+    }
+
+    fn on_event(
+        &mut self,
+        _event: Event,
+        _layout: Layout<'_>,
+        _cursor_position: Point,
+        _renderer: &Renderer,
+        _clipboard: &mut dyn Clipboard,
+        messages: &mut Vec<Message>,
+    ) -> event::Status {
+        if self.state.is_near.get() {
+            messages.push(self.on_near.clone());
+        }
+        event::Status::Ignored
+    }
+}
+
+impl<'a, Message, Renderer> From<ScrollSentinel<'a, Message>> for Element<'a, Message, Renderer>
+where
+    Message: 'a + Clone,
+    Renderer: 'a + space::Renderer,
+{
+    fn from(sentinel: ScrollSentinel<'a, Message>) -> Element<'a, Message, Renderer> {
+        Element::new(sentinel)
+    }
+}
@@ -1,14 +1,18 @@
-use super::{CoursesWidget, UploadsWidget};
+use super::{CoursesWidget, FavoritesWidget, InboxWidget, ProfileWidget, UploadsWidget};
 use crate::{font, styles::*, AppState, Message, Smmdb};
 
 use iced::{
     button, scrollable, Align, Button, Container, Element, Length, Row, Scrollable, Space, Text,
 };
+use std::collections::HashMap;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum SmmdbTab {
     Courses,
     Uploads,
+    Favorites,
+    Inbox,
+    Profile,
 }
 
 #[derive(Clone, Debug)]
@@ -16,9 +20,15 @@ pub struct SmmdbWidget {
     tab: SmmdbTab,
     courses_widget: CoursesWidget,
     uploads_widget: UploadsWidget,
+    favorites_widget: FavoritesWidget,
+    inbox_widget: InboxWidget,
+    profile_widget: ProfileWidget,
     state: scrollable::State,
     courses_state: button::State,
     uploads_state: button::State,
+    favorites_state: button::State,
+    inbox_state: button::State,
+    profile_state: button::State,
 }
 
 impl SmmdbWidget {
@@ -27,9 +37,15 @@ impl SmmdbWidget {
             tab: SmmdbTab::Courses,
             courses_widget: CoursesWidget::new(),
             uploads_widget: UploadsWidget::new(),
+            favorites_widget: FavoritesWidget::new(),
+            inbox_widget: InboxWidget::new(),
+            profile_widget: ProfileWidget::new(),
             state: scrollable::State::new(),
             courses_state: button::State::new(),
             uploads_state: button::State::new(),
+            favorites_state: button::State::new(),
+            inbox_state: button::State::new(),
+            profile_state: button::State::new(),
         }
     }
 
@@ -41,6 +57,7 @@ impl SmmdbWidget {
         &'a mut self,
         state: &AppState,
         smmdb: &'a mut Smmdb,
+        installed_courses: &HashMap<String, Vec<usize>>,
     ) -> impl Into<Element<crate::Message>> {
         let courses_button = Button::new(&mut self.courses_state, Text::new("Courses".to_string()))
             .style(TabButtonStyle(self.tab == SmmdbTab::Courses))
@@ -51,16 +68,67 @@ impl SmmdbWidget {
             .padding(TAB_BUTTON_PADDING)
             .on_press(Message::SetSmmdbTab(SmmdbTab::Uploads));
 
-        let tab_buttons = Row::new()
+        let favorites_button = Button::new(
+            &mut self.favorites_state,
+            Text::new("Favorites".to_string()),
+        )
+        .style(TabButtonStyle(self.tab == SmmdbTab::Favorites))
+        .padding(TAB_BUTTON_PADDING)
+        .on_press(Message::SetSmmdbTab(SmmdbTab::Favorites));
+        let inbox_len = smmdb.get_inbox_mut().len();
+        let inbox_button = Button::new(
+            &mut self.inbox_state,
+            Text::new(if inbox_len > 0 {
+                format!("Inbox ({})", inbox_len)
+            } else {
+                "Inbox".to_string()
+            }),
+        )
+        .style(TabButtonStyle(self.tab == SmmdbTab::Inbox))
+        .padding(TAB_BUTTON_PADDING)
+        .on_press(Message::SetSmmdbTab(SmmdbTab::Inbox));
+
+        let mut tab_buttons = Row::new()
             .padding(CONTAINER_PADDING)
             .spacing(LIST_SPACING)
             .align_items(Align::Center)
             .push(courses_button)
-            .push(uploads_button);
+            .push(uploads_button)
+            .push(favorites_button)
+            .push(inbox_button);
+        if let Some(profile) = smmdb.get_profile_mut() {
+            tab_buttons = tab_buttons.push(
+                Button::new(
+                    &mut self.profile_state,
+                    Text::new(profile.get_uploader().clone()),
+                )
+                .style(TabButtonStyle(self.tab == SmmdbTab::Profile))
+                .padding(TAB_BUTTON_PADDING)
+                .on_press(Message::SetSmmdbTab(SmmdbTab::Profile)),
+            );
+        }
 
         let tab_content = Container::new(match self.tab {
-            SmmdbTab::Courses => self.courses_widget.view(state, smmdb).into(),
-            SmmdbTab::Uploads => self.uploads_widget.view(state, smmdb).into(),
+            SmmdbTab::Courses => self
+                .courses_widget
+                .view(state, smmdb, installed_courses)
+                .into(),
+            SmmdbTab::Uploads => self
+                .uploads_widget
+                .view(state, smmdb, installed_courses)
+                .into(),
+            SmmdbTab::Favorites => self
+                .favorites_widget
+                .view(state, smmdb, installed_courses)
+                .into(),
+            SmmdbTab::Inbox => self
+                .inbox_widget
+                .view(state, smmdb, installed_courses)
+                .into(),
+            SmmdbTab::Profile => self
+                .profile_widget
+                .view(state, smmdb, installed_courses)
+                .into(),
         })
         .width(Length::Fill)
         .style(TabContainerStyle);
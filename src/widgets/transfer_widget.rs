@@ -0,0 +1,229 @@
+use crate::{font, styles::*, AppState, Message};
+
+use iced::{button, pick_list, Align, Button, Column, Element, Length, PickList, Row, Space, Text};
+use smmdb_lib::CourseEntry;
+use std::fmt::{self, Display};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransferDirection {
+    LeftToRight,
+    RightToLeft,
+}
+
+impl TransferDirection {
+    pub const ALL: [TransferDirection; 2] = [
+        TransferDirection::LeftToRight,
+        TransferDirection::RightToLeft,
+    ];
+}
+
+impl Display for TransferDirection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TransferDirection::LeftToRight => write!(f, "Left to right"),
+            TransferDirection::RightToLeft => write!(f, "Right to left"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SlotOption {
+    pub index: u8,
+    pub label: String,
+}
+
+impl SlotOption {
+    pub fn from_save(save: &smmdb_lib::Save) -> Vec<SlotOption> {
+        save.get_own_courses()
+            .iter()
+            .enumerate()
+            .map(|(index, course)| {
+                let title = match course.as_deref() {
+                    Some(CourseEntry::SavedCourse(course)) => course
+                        .get_course()
+                        .get_course()
+                        .get_header()
+                        .get_title()
+                        .to_string(),
+                    Some(CourseEntry::CorruptedCourse(_)) => "(corrupted)".to_string(),
+                    None => "(empty)".to_string(),
+                };
+                SlotOption {
+                    index: index as u8,
+                    label: format!("{}: {}", index + 1, title),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Display for SlotOption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+/// Lets the user copy or move a course between two opened saves.
+#[derive(Clone, Debug)]
+pub struct TransferWidget {
+    direction: TransferDirection,
+    left_slots: Vec<SlotOption>,
+    right_slots: Vec<SlotOption>,
+    source_slot: Option<SlotOption>,
+    target_slot: Option<SlotOption>,
+    direction_state: pick_list::State<TransferDirection>,
+    source_state: pick_list::State<SlotOption>,
+    target_state: pick_list::State<SlotOption>,
+    copy_state: button::State,
+    move_state: button::State,
+    compare_state: button::State,
+    close_state: button::State,
+}
+
+impl TransferWidget {
+    pub fn new(left: &smmdb_lib::Save, right: &smmdb_lib::Save) -> TransferWidget {
+        TransferWidget {
+            direction: TransferDirection::LeftToRight,
+            left_slots: SlotOption::from_save(left),
+            right_slots: SlotOption::from_save(right),
+            source_slot: None,
+            target_slot: None,
+            direction_state: pick_list::State::default(),
+            source_state: pick_list::State::default(),
+            target_state: pick_list::State::default(),
+            copy_state: button::State::new(),
+            move_state: button::State::new(),
+            compare_state: button::State::new(),
+            close_state: button::State::new(),
+        }
+    }
+
+    /// Updates the slot labels after one of the saves has changed.
+    pub fn set_saves(&mut self, left: &smmdb_lib::Save, right: &smmdb_lib::Save) {
+        self.left_slots = SlotOption::from_save(left);
+        self.right_slots = SlotOption::from_save(right);
+        let (source_slots, target_slots) = self.get_slots();
+        let source_slot = self
+            .source_slot
+            .as_ref()
+            .and_then(|slot| source_slots.get(slot.index as usize).cloned());
+        let target_slot = self
+            .target_slot
+            .as_ref()
+            .and_then(|slot| target_slots.get(slot.index as usize).cloned());
+        self.source_slot = source_slot;
+        self.target_slot = target_slot;
+    }
+
+    pub fn get_direction(&self) -> TransferDirection {
+        self.direction
+    }
+
+    pub fn set_direction(&mut self, direction: TransferDirection) {
+        if self.direction != direction {
+            self.direction = direction;
+            self.source_slot = None;
+            self.target_slot = None;
+        }
+    }
+
+    pub fn get_selection(&self) -> Option<(u8, u8)> {
+        match (&self.source_slot, &self.target_slot) {
+            (Some(source), Some(target)) => Some((source.index, target.index)),
+            _ => None,
+        }
+    }
+
+    pub fn set_source_slot(&mut self, slot: SlotOption) {
+        self.source_slot = Some(slot);
+    }
+
+    pub fn set_target_slot(&mut self, slot: SlotOption) {
+        self.target_slot = Some(slot);
+    }
+
+    fn get_slots(&self) -> (&Vec<SlotOption>, &Vec<SlotOption>) {
+        match self.direction {
+            TransferDirection::LeftToRight => (&self.left_slots, &self.right_slots),
+            TransferDirection::RightToLeft => (&self.right_slots, &self.left_slots),
+        }
+    }
+
+    pub fn view(&mut self, state: &AppState) -> Element<Message> {
+        let (source_slots, target_slots) = match self.direction {
+            TransferDirection::LeftToRight => (&self.left_slots, &self.right_slots),
+            TransferDirection::RightToLeft => (&self.right_slots, &self.left_slots),
+        };
+        let has_selection = self.source_slot.is_some() && self.target_slot.is_some();
+
+        let mut copy_button = Button::new(&mut self.copy_state, Text::new("Copy"))
+            .padding(BUTTON_PADDING)
+            .style(DefaultButtonStyle);
+        let mut move_button = Button::new(&mut self.move_state, Text::new("Move"))
+            .padding(BUTTON_PADDING)
+            .style(DefaultButtonDangerStyle);
+        if has_selection && state != &AppState::Loading {
+            copy_button = copy_button.on_press(Message::TransferCourse(false));
+            move_button = move_button.on_press(Message::TransferCourse(true));
+        }
+
+        Column::new()
+            .padding(CONTAINER_PADDING)
+            .spacing(LIST_SPACING)
+            .push(Text::new("Transfer courses:").font(font::HELVETICA_BOLD))
+            .push(
+                Row::new()
+                    .align_items(Align::Center)
+                    .spacing(LIST_SPACING)
+                    .push(
+                        PickList::new(
+                            &mut self.direction_state,
+                            &TransferDirection::ALL[..],
+                            Some(self.direction),
+                            Message::TransferDirectionChanged,
+                        )
+                        .style(DefaultPickListStyle)
+                        .padding(4),
+                    )
+                    .push(
+                        PickList::new(
+                            &mut self.source_state,
+                            &source_slots[..],
+                            self.source_slot.clone(),
+                            Message::TransferSourceChanged,
+                        )
+                        .style(DefaultPickListStyle)
+                        .padding(4)
+                        .width(Length::Units(240)),
+                    )
+                    .push(Text::new("to"))
+                    .push(
+                        PickList::new(
+                            &mut self.target_state,
+                            &target_slots[..],
+                            self.target_slot.clone(),
+                            Message::TransferTargetChanged,
+                        )
+                        .style(DefaultPickListStyle)
+                        .padding(4)
+                        .width(Length::Units(240)),
+                    )
+                    .push(copy_button)
+                    .push(move_button)
+                    .push(Space::with_width(Length::Fill))
+                    .push(
+                        Button::new(&mut self.compare_state, Text::new("Compare saves"))
+                            .padding(BUTTON_PADDING)
+                            .style(DefaultButtonStyle)
+                            .on_press(Message::CompareSaves),
+                    )
+                    .push(
+                        Button::new(&mut self.close_state, Text::new("Close second save"))
+                            .padding(BUTTON_PADDING)
+                            .style(DefaultButtonStyle)
+                            .on_press(Message::CloseSecondSave),
+                    ),
+            )
+            .into()
+    }
+}
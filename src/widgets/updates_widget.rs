@@ -0,0 +1,137 @@
+use crate::{font, smmdb::CourseUpdate, styles::*, AppState, Message};
+
+use iced::{button, Align, Button, Column, Element, Length, Row, Space, Text};
+
+/// Lists installed courses, which have been modified on SMMDB since they have been downloaded or
+/// whose downloaded version is unknown.
+#[derive(Clone, Debug)]
+pub struct UpdatesWidget {
+    /// `None` while the save is being checked for updates.
+    updates: Option<Vec<CourseUpdate>>,
+    /// Updates all listed courses one after another.
+    is_updating_all: bool,
+    update_states: Vec<button::State>,
+    update_all_state: button::State,
+    close_state: button::State,
+}
+
+impl UpdatesWidget {
+    pub fn new() -> UpdatesWidget {
+        UpdatesWidget {
+            updates: None,
+            is_updating_all: false,
+            update_states: vec![],
+            update_all_state: button::State::new(),
+            close_state: button::State::new(),
+        }
+    }
+
+    pub fn set_updates(&mut self, updates: Vec<CourseUpdate>) {
+        self.update_states
+            .resize_with(updates.len(), Default::default);
+        self.updates = Some(updates);
+        self.is_updating_all = false;
+    }
+
+    /// Returns the first update, after which all remaining updates will be installed.
+    pub fn start_update_all(&mut self) -> Option<CourseUpdate> {
+        let update = self.updates.as_ref()?.first().cloned();
+        self.is_updating_all = update.is_some();
+        update
+    }
+
+    /// Removes the update of a slot, whose course has just been replaced, and returns the next
+    /// update, if all updates are being installed.
+    pub fn finish_update(&mut self, slot: usize) -> Option<CourseUpdate> {
+        let updates = self.updates.as_mut()?;
+        updates.retain(|update| update.slot != slot);
+        self.update_states.truncate(updates.len());
+        if !self.is_updating_all {
+            return None;
+        }
+        let update = updates.first().cloned();
+        self.is_updating_all = update.is_some();
+        update
+    }
+
+    pub fn stop_update_all(&mut self) {
+        self.is_updating_all = false;
+    }
+
+    pub fn view(&mut self, state: &AppState, read_only: bool) -> Element<Message> {
+        let can_update = state == &AppState::Default && !read_only;
+        let mut update_all_button =
+            Button::new(&mut self.update_all_state, Text::new("Update all"))
+                .padding(BUTTON_PADDING)
+                .style(DefaultButtonStyle);
+        if can_update
+            && self
+                .updates
+                .as_ref()
+                .map_or(false, |updates| !updates.is_empty())
+        {
+            update_all_button = update_all_button.on_press(Message::UpdateAllCourses);
+        }
+        let header = Row::new()
+            .align_items(Align::Center)
+            .spacing(LIST_SPACING)
+            .push(Text::new("Course updates:").font(font::HELVETICA_BOLD))
+            .push(Space::with_width(Length::Fill))
+            .push(update_all_button)
+            .push(
+                Button::new(&mut self.close_state, Text::new("Close"))
+                    .padding(BUTTON_PADDING)
+                    .style(DefaultButtonStyle)
+                    .on_press(Message::CloseCourseUpdates),
+            );
+
+        let content = Column::new()
+            .padding(CONTAINER_PADDING)
+            .spacing(LIST_SPACING)
+            .push(header);
+        let updates = match &self.updates {
+            Some(updates) => updates,
+            None => {
+                return content
+                    .push(Text::new("Checking for updates…").color(TEXT_HELP_COLOR))
+                    .into()
+            }
+        };
+        if updates.is_empty() {
+            return content
+                .push(
+                    Text::new("All courses downloaded from SMMDB are up to date.")
+                        .color(TEXT_HELP_COLOR),
+                )
+                .into();
+        }
+        updates
+            .iter()
+            .zip(self.update_states.iter_mut())
+            .fold(content, |acc, (update, update_state)| {
+                let mut update_button = Button::new(update_state, Text::new("Update").size(14))
+                    .style(DefaultButtonStyle);
+                if can_update {
+                    update_button = update_button.on_press(Message::DownloadCourse(
+                        update.slot,
+                        update.smmdb_id.clone(),
+                    ));
+                }
+                let mut row = Row::new()
+                    .spacing(LIST_SPACING)
+                    .align_items(Align::Center)
+                    .push(
+                        Text::new(format!("Slot {}: {}", update.slot + 1, update.title)).size(16),
+                    );
+                if update.is_version_unknown {
+                    row = row.push(
+                        Text::new("(installed version unknown)")
+                            .size(14)
+                            .color(TEXT_HELP_COLOR),
+                    );
+                }
+                acc.push(row.push(update_button))
+            })
+            .into()
+    }
+}
@@ -8,6 +8,7 @@ use crate::{
 use iced::{
     button, pick_list, text_input, Align, Button, Column, Element, Length, Row, Space, Text,
 };
+use std::collections::HashMap;
 
 #[derive(Clone, Debug)]
 pub struct UploadsWidget {
@@ -37,6 +38,7 @@ impl UploadsWidget {
         &'a mut self,
         state: &AppState,
         smmdb: &'a mut Smmdb,
+        installed_courses: &HashMap<String, Vec<usize>>,
     ) -> impl Into<Element<Message>> {
         let mut content = Column::new()
             .padding(TAB_PADDING)
@@ -110,8 +112,18 @@ impl UploadsWidget {
                 .push(forward_button);
 
             let smmdb_user = smmdb.get_user().cloned();
+            let favorite_ids = smmdb.get_favorite_ids();
             for panel in smmdb.get_own_course_panels().values_mut() {
-                content = content.push(panel.view(state, smmdb_user.as_ref()));
+                let is_favorite = favorite_ids.contains(panel.get_id());
+                let installed_slots = installed_courses
+                    .get(panel.get_id())
+                    .map_or(&[][..], |slots| &slots[..]);
+                content = content.push(panel.view(
+                    state,
+                    smmdb_user.as_ref(),
+                    is_favorite,
+                    installed_slots,
+                ));
             }
 
             content.push(paginator)